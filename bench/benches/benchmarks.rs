@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+use std::fs;
+
+use bench::Harness;
+use intcode::IntcodeComputer;
+use utils::bfs::bfs;
+use utils::parse_grid;
+use utils::v2::V2;
+
+// Every part of every day on its checked in input, then a few of the building blocks on their
+// own. Run with `cargo bench -p bench`, adding `-- --help` for the options.
+fn main() {
+    let mut harness = Harness::from_args();
+
+    for entry in solutions::all() {
+        let input = read_input(entry.day);
+        for &part in entry.parts {
+            harness.bench(&format!("d{:02}/{}", entry.day, part), || {
+                entry.run(part, &input)
+            });
+        }
+    }
+
+    // day 9's BOOST program in sensor mode, which uses every instruction there is
+    let program = IntcodeComputer::parse_program(&read_input(9));
+    harness.bench_with_setup(
+        "intcode/run",
+        || IntcodeComputer::new(program.clone(), Some(vec![2])),
+        |mut computer| {
+            computer.run();
+            computer.output
+        },
+    );
+
+    // searching the whole of day 18's maze, since nothing counts as done
+    let map = read_input(18);
+    let open: HashSet<V2> = parse_grid(&map)
+        .filter(|&(_, c)| c != '#')
+        .map(|(pos, _)| pos)
+        .collect();
+    let start = parse_grid(&map).find(|&(_, c)| c == '@').unwrap().0;
+    harness.bench("bfs/maze", || {
+        let neighbours = |pos: &V2| {
+            pos.taxicab_neighbours()
+                .into_iter()
+                .filter(|p| open.contains(p))
+                .collect()
+        };
+        bfs(neighbours, |_| false, start)
+    });
+
+    harness.bench("parse_grid", || {
+        parse_grid(&map).filter(|&(_, c)| c == '#').count()
+    });
+
+    harness.finish();
+}
+
+fn read_input(day: u8) -> String {
+    let path = format!(
+        "{}/../d{:02}/src/input.txt",
+        env!("CARGO_MANIFEST_DIR"),
+        day
+    );
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("couldn't read {}: {}", path, e))
+}
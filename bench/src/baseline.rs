@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::{fs, io};
+
+use utils::parse::{Input, ParseError};
+
+use crate::stats::Stats;
+
+// A saved set of results, so a later run can be compared with it. The file has a line per
+// benchmark, with its name then the numbers from `Stats` in the order they're declared, all
+// separated by tabs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline(BTreeMap<String, Stats>);
+
+const HEADER: &str = "# name\tsamples\tmean\tmedian\tstd_dev\tmin\tmax (times in ns)";
+
+impl Baseline {
+    pub fn get(&self, name: &str) -> Option<&Stats> {
+        self.0.get(name)
+    }
+
+    pub fn insert(&mut self, name: &str, stats: Stats) {
+        self.0.insert(name.to_string(), stats);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Stats)> {
+        self.0.iter().map(|(name, stats)| (name.as_str(), stats))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let input = Input::new(text);
+        let mut baseline = Self::default();
+        for line in input.lines().filter(|line| !line.starts_with('#')) {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 7 {
+                let message = format!("expected 7 fields, found {}", fields.len());
+                return Err(input.error(line, message));
+            }
+            let number = |i: usize| input.number::<f64>(fields[i]);
+            let stats = Stats {
+                samples: input.number(fields[1])?,
+                mean: number(2)?,
+                median: number(3)?,
+                std_dev: number(4)?,
+                min: number(5)?,
+                max: number(6)?,
+            };
+            baseline.insert(fields[0], stats);
+        }
+        Ok(baseline)
+    }
+
+    // Nothing saved yet is the same as an empty baseline
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for (name, s) in self.iter() {
+            writeln!(
+                text,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                name, s.samples, s.mean, s.median, s.std_dev, s.min, s.max
+            )
+            .unwrap();
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.insert("d01/a", Stats::new(&[1.5, 2.0, 10.25]));
+        baseline.insert("intcode/run", Stats::new(&[1e6]));
+        let text = baseline.to_text();
+        assert!(text.starts_with("# name"));
+        assert_eq!(Baseline::parse(&text), Ok(baseline));
+        let error = Baseline::parse("d01/a\t1\t2\n").unwrap_err();
+        assert_eq!(error.message, "expected 7 fields, found 3");
+        let error = Baseline::parse("d01/a\t1\t2\t3\tx\t5\t6\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 13));
+    }
+}
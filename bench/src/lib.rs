@@ -0,0 +1,285 @@
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+mod baseline;
+mod stats;
+
+pub use baseline::Baseline;
+pub use stats::{format_nanos, Change, Stats, Verdict};
+
+// A small benchmark harness along the same lines as criterion: warm each benchmark up, take a
+// few dozen timed samples, and save the results, so that the next run can say whether anything
+// got faster or slower. Everything is kept under target/benchmarks, one file per baseline.
+
+pub const USAGE: &str = "usage: cargo bench -p bench -- [filter] [options]
+
+  filter                   only run benchmarks whose names contain this
+  --quick                  take fewer, shorter samples
+  --save-baseline <name>   save the results as <name> (`latest` by default)
+  --baseline <name>        compare with <name> rather than the last results saved
+                           under the same name
+  --compare <old> <new>    compare two saved baselines, without running anything";
+
+// config {{{1
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    pub warm_up: Duration,
+    // roughly how long to spend taking samples, unless a single iteration takes longer
+    pub measurement: Duration,
+    pub samples: usize,
+    // fewer than this isn't worth summarising, however slow the benchmark is
+    pub min_samples: usize,
+}
+
+impl Config {
+    pub const STANDARD: Self = Self {
+        warm_up: Duration::from_secs(1),
+        measurement: Duration::from_secs(3),
+        samples: 50,
+        min_samples: 10,
+    };
+
+    pub const QUICK: Self = Self {
+        warm_up: Duration::from_millis(100),
+        measurement: Duration::from_millis(500),
+        samples: 20,
+        min_samples: 3,
+    };
+}
+
+// measurement {{{1
+// Times `routine`, giving it a fresh value from `setup` each time, without counting the time
+// spent in `setup`.
+pub fn measure<I, O, SetupFn, RoutineFn>(
+    config: &Config,
+    mut setup: SetupFn,
+    mut routine: RoutineFn,
+) -> Stats
+where
+    SetupFn: FnMut() -> I,
+    RoutineFn: FnMut(I) -> O,
+{
+    // warm up, and work out roughly how long one iteration takes
+    let start = Instant::now();
+    let mut iterations = 0;
+    let mut busy = Duration::ZERO;
+    while iterations == 0 || start.elapsed() < config.warm_up {
+        let input = setup();
+        let t = Instant::now();
+        black_box(routine(black_box(input)));
+        busy += t.elapsed();
+        iterations += 1;
+    }
+    let per_iteration = (busy.as_secs_f64() / iterations as f64).max(1e-9);
+
+    let budget = config.measurement.as_secs_f64();
+    let samples = ((budget / per_iteration) as usize).clamp(config.min_samples, config.samples);
+    let iterations_per_sample = ((budget / samples as f64 / per_iteration) as usize).max(1);
+    let times: Vec<f64> = (0..samples)
+        .map(|_| {
+            let inputs: Vec<I> = (0..iterations_per_sample).map(|_| setup()).collect();
+            let t = Instant::now();
+            for input in inputs {
+                black_box(routine(black_box(input)));
+            }
+            t.elapsed().as_secs_f64() * 1e9 / iterations_per_sample as f64
+        })
+        .collect();
+    Stats::new(&times)
+}
+
+// harness {{{1
+pub struct Harness {
+    config: Config,
+    filter: Option<String>,
+    save_as: String,
+    // what each benchmark gets compared with
+    previous: Baseline,
+    compared_with: String,
+    results: Baseline,
+}
+
+impl Harness {
+    // Set up from the command line, exiting if it doesn't make sense. `cargo bench` adds a
+    // `--bench` of its own, which is ignored.
+    pub fn from_args() -> Self {
+        let mut config = Config::STANDARD;
+        let mut filter = None;
+        let mut save_as = "latest".to_string();
+        let mut baseline = None;
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().unwrap_or_else(|| usage_error(&arg));
+            match arg.as_str() {
+                "--bench" => {}
+                "--help" => {
+                    println!("{}", USAGE);
+                    process::exit(0);
+                }
+                "--quick" => config = Config::QUICK,
+                "--save-baseline" => save_as = value(),
+                "--baseline" => baseline = Some(value()),
+                "--compare" => {
+                    let old = value();
+                    let new = args.next().unwrap_or_else(|| usage_error("--compare"));
+                    compare(&old, &new);
+                    process::exit(0);
+                }
+                _ if arg.starts_with('-') => usage_error(&arg),
+                _ => filter = Some(arg),
+            }
+        }
+        let compared_with = baseline.unwrap_or_else(|| save_as.clone());
+        Self {
+            config,
+            filter,
+            previous: load(&compared_with),
+            compared_with,
+            save_as,
+            results: Baseline::default(),
+        }
+    }
+
+    pub fn bench<O, RoutineFn>(&mut self, name: &str, mut routine: RoutineFn)
+    where
+        RoutineFn: FnMut() -> O,
+    {
+        self.bench_with_setup(name, || (), |()| routine());
+    }
+
+    // Like `bench`, for a routine that uses up its input, like a computer that gets run
+    pub fn bench_with_setup<I, O, SetupFn, RoutineFn>(
+        &mut self,
+        name: &str,
+        setup: SetupFn,
+        routine: RoutineFn,
+    ) where
+        SetupFn: FnMut() -> I,
+        RoutineFn: FnMut(I) -> O,
+    {
+        if self.filter.as_ref().is_some_and(|f| !name.contains(f)) {
+            return;
+        }
+        let stats = measure(&self.config, setup, routine);
+        let change = self
+            .previous
+            .get(name)
+            .map(|old| Change::between(old, &stats));
+        print_result(name, &stats, change, &self.compared_with);
+        self.results.insert(name, stats);
+    }
+
+    // Saves the results, keeping anything saved under the same name that wasn't run this time
+    pub fn finish(self) {
+        let path = baseline_path(&self.save_as);
+        let mut saved = load(&self.save_as);
+        for (name, stats) in self.results.iter() {
+            saved.insert(name, *stats);
+        }
+        if let Err(e) = saved.save(&path) {
+            eprintln!("couldn't save {}: {}", path.display(), e);
+            process::exit(1);
+        }
+        println!("\nsaved as `{}` in {}", self.save_as, path.display());
+    }
+}
+
+fn print_result(name: &str, stats: &Stats, change: Option<Change>, compared_with: &str) {
+    let change = change.map_or_else(String::new, |c| {
+        format!("{} against `{}`", c, compared_with)
+    });
+    let line = format!(
+        "{:<16} {:>22}  [{} samples]  {}",
+        name,
+        stats.to_string(),
+        stats.samples,
+        change
+    );
+    println!("{}", line.trim_end());
+}
+
+// Prints how every benchmark in both baselines changed between them
+fn compare(old: &str, new: &str) {
+    let (old_results, new_results) = (load(old), load(new));
+    for (name, results) in [(old, &old_results), (new, &new_results)] {
+        if results.iter().next().is_none() {
+            eprintln!("nothing has been saved as `{}`", name);
+            process::exit(1);
+        }
+    }
+    println!("{:<16} {:>12} {:>12}  change", "", old, new);
+    for (name, stats) in new_results.iter() {
+        if let Some(old_stats) = old_results.get(name) {
+            println!(
+                "{:<16} {:>12} {:>12}  {}",
+                name,
+                format_nanos(old_stats.median),
+                format_nanos(stats.median),
+                Change::between(old_stats, stats)
+            );
+        }
+    }
+}
+
+fn baseline_path(name: &str) -> PathBuf {
+    let target = std::env::var_os("CARGO_TARGET_DIR").map_or_else(
+        || {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .unwrap()
+                .join("target")
+        },
+        PathBuf::from,
+    );
+    target.join("benchmarks").join(format!("{}.tsv", name))
+}
+
+fn load(name: &str) -> Baseline {
+    let path = baseline_path(name);
+    Baseline::load(&path).unwrap_or_else(|e| {
+        eprintln!("couldn't load {}: {}", path.display(), e);
+        process::exit(1);
+    })
+}
+
+fn usage_error(arg: &str) -> ! {
+    eprintln!("unexpected `{}`\n\n{}", arg, USAGE);
+    process::exit(2);
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measuring() {
+        let config = Config {
+            warm_up: Duration::ZERO,
+            measurement: Duration::from_millis(20),
+            samples: 10,
+            min_samples: 3,
+        };
+        let stats = measure(
+            &config,
+            || (),
+            |()| std::thread::sleep(Duration::from_millis(2)),
+        );
+        // too slow for all ten samples to fit in the time
+        assert!((3..10).contains(&stats.samples), "{:?}", stats);
+        assert!(stats.min >= 2e6);
+        let mut setups = 0;
+        let stats = measure(
+            &config,
+            || {
+                setups += 1;
+                vec![1u64; 1000]
+            },
+            |v| v.into_iter().sum::<u64>(),
+        );
+        assert_eq!(stats.samples, 10);
+        assert!(setups > 10);
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+use std::time::Duration;
+
+// A summary of how long each iteration took, over every sample, in nanoseconds
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Stats {
+    pub fn new(times: &[f64]) -> Self {
+        assert!(!times.is_empty(), "no samples to summarise");
+        let mut sorted = times.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        } else {
+            sorted[n / 2]
+        };
+        let variance = sorted.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / n as f64;
+        Self {
+            samples: n,
+            mean,
+            median,
+            std_dev: variance.sqrt(),
+            min: sorted[0],
+            max: sorted[n - 1],
+        }
+    }
+
+    // How noisy the samples were, as a fraction of the mean
+    pub fn relative_std_dev(&self) -> f64 {
+        if self.mean > 0.0 {
+            self.std_dev / self.mean
+        } else {
+            0.0
+        }
+    }
+}
+
+// The median, with how far the samples spread either side of the mean
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} ± {}",
+            format_nanos(self.median),
+            format_nanos(self.std_dev)
+        )
+    }
+}
+
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.2?}", Duration::from_secs_f64(nanos.max(0.0) / 1e9))
+}
+
+// comparisons {{{1
+// Changes smaller than this are put down to noise, whatever the samples say
+pub const NOISE_THRESHOLD: f64 = 0.05;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Improved,
+    Regressed,
+    NoChange,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Change {
+    // how much the median moved, as a fraction of the old one
+    pub ratio: f64,
+    pub verdict: Verdict,
+}
+
+impl Change {
+    // A change only counts if it's bigger than both the threshold and the noise in either run
+    pub fn between(old: &Stats, new: &Stats) -> Self {
+        let ratio = new.median / old.median - 1.0;
+        let noise = NOISE_THRESHOLD
+            .max(old.relative_std_dev())
+            .max(new.relative_std_dev());
+        let verdict = if ratio.abs() <= noise {
+            Verdict::NoChange
+        } else if ratio < 0.0 {
+            Verdict::Improved
+        } else {
+            Verdict::Regressed
+        };
+        Self { ratio, verdict }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let verdict = match self.verdict {
+            Verdict::Improved => "improved",
+            Verdict::Regressed => "REGRESSED",
+            Verdict::NoChange => "no change",
+        };
+        write!(f, "{:+.2}% ({})", self.ratio * 100.0, verdict)
+    }
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let stats = Stats::new(&[4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.mean, 2.5);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!((stats.min, stats.max), (1.0, 4.0));
+        assert!((stats.std_dev - 1.25f64.sqrt()).abs() < 1e-9);
+        assert_eq!(Stats::new(&[5.0, 1.0, 3.0]).median, 3.0);
+        assert_eq!(Stats::new(&[1500.0]).to_string(), "1.50µs ± 0.00ns");
+    }
+
+    #[test]
+    fn changes() {
+        let steady = |median| Stats::new(&[median; 5]);
+        let change = Change::between(&steady(100.0), &steady(80.0));
+        assert_eq!(change.verdict, Verdict::Improved);
+        assert_eq!(change.to_string(), "-20.00% (improved)");
+        let change = Change::between(&steady(100.0), &steady(103.0));
+        assert_eq!(change.verdict, Verdict::NoChange);
+        let change = Change::between(&steady(100.0), &steady(150.0));
+        assert_eq!(change.verdict, Verdict::Regressed);
+        // too noisy to tell
+        let noisy = Stats::new(&[20.0, 110.0, 110.0, 200.0]);
+        assert_eq!(
+            Change::between(&steady(100.0), &noisy).verdict,
+            Verdict::NoChange
+        );
+    }
+}
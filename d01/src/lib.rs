@@ -1,17 +1,13 @@
-pub fn part_a(input: &str) -> i64 {
-    input
-        .trim()
-        .lines()
-        .map(|l| fuel(l.parse::<i64>().unwrap()))
-        .sum()
+use utils::parse::{numbers_per_line, ParseError};
+
+pub fn part_a(input: &str) -> Result<i64, ParseError> {
+    let masses: Vec<i64> = numbers_per_line(input)?;
+    Ok(masses.into_iter().map(fuel).sum())
 }
 
-pub fn part_b(input: &str) -> i64 {
-    input
-        .trim()
-        .lines()
-        .map(|l| fuel_recursive(l.parse::<i64>().unwrap()))
-        .sum()
+pub fn part_b(input: &str) -> Result<i64, ParseError> {
+    let masses: Vec<i64> = numbers_per_line(input)?;
+    Ok(masses.into_iter().map(fuel_recursive).sum())
 }
 
 const fn fuel(n: i64) -> i64 {
@@ -27,26 +23,32 @@ fn fuel_recursive(n: i64) -> i64 {
     .sum()
 }
 
+utils::solution! {
+    Day01 = 1;
+    a: |input| Ok(part_a(input)?),
+    b: |input| Ok(part_b(input)?),
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_a() {
-        assert_eq!(super::part_a("12"), 2);
-        assert_eq!(super::part_a("14"), 2);
-        assert_eq!(super::part_a("1969"), 654);
-        assert_eq!(super::part_a("100756"), 33583);
+        assert_eq!(super::part_a("12"), Ok(2));
+        assert_eq!(super::part_a("14"), Ok(2));
+        assert_eq!(super::part_a("1969"), Ok(654));
+        assert_eq!(super::part_a("100756"), Ok(33583));
     }
 
     #[test]
     fn example_b() {
-        assert_eq!(super::part_a("14"), 2);
-        assert_eq!(super::part_a("1969"), 654);
-        assert_eq!(super::part_b("100756"), 50346);
+        assert_eq!(super::part_a("14"), Ok(2));
+        assert_eq!(super::part_a("1969"), Ok(654));
+        assert_eq!(super::part_b("100756"), Ok(50346));
     }
 
     #[test]
     fn real() {
-        assert_eq!(super::part_a(include_str!("input.txt")), 3216868);
-        assert_eq!(super::part_b(include_str!("input.txt")), 4822435);
+        assert_eq!(super::part_a(include_str!("input.txt")), Ok(3216868));
+        assert_eq!(super::part_b(include_str!("input.txt")), Ok(4822435));
     }
 }
@@ -1,4 +1,5 @@
-use intcode::IntcodeComputer;
+use intcode::{symbolic::SymbolicComputer, IntcodeComputer};
+use utils::solution::SolutionError;
 
 pub fn part_a(input: &str) -> i64 {
     let program = {
@@ -11,18 +12,23 @@ pub fn part_a(input: &str) -> i64 {
 }
 
 pub fn part_b(input: &str) -> Option<i64> {
-    let mut start_program = IntcodeComputer::parse_program(input);
-    for noun in 0..=99 {
-        start_program[1] = noun;
-        for verb in 0..=99 {
-            start_program[2] = verb;
-            let computer = IntcodeComputer::run_program(start_program.clone(), None);
-            if computer.data[&0] == 19_690_720 {
-                return Some(100 * noun + verb);
-            }
-        }
-    }
-    None
+    let program = IntcodeComputer::parse_program(input);
+    let mut computer = SymbolicComputer::new(program, None);
+    let noun = computer.make_symbolic(1);
+    let verb = computer.make_symbolic(2);
+    computer.run().ok()?;
+    let solution = computer
+        .get_mem(0)
+        .ok()?
+        .solve(19_690_720, &[(noun, 0..=99), (verb, 0..=99)])?;
+    Some(100 * solution[&noun] + solution[&verb])
+}
+
+utils::solution! {
+    Day02 = 2, check IntcodeComputer::try_parse_program;
+    a: |input| Ok(part_a(input)),
+    b: |input| part_b(input)
+        .ok_or_else(|| SolutionError::no_answer("no noun and verb give 19690720")),
 }
 
 #[cfg(test)]
@@ -1,53 +1,71 @@
 use std::collections::HashSet;
 
-use utils::v2::V2;
+use utils::{
+    parse::{Input, ParseError},
+    solution::SolutionError,
+    v2::V2,
+};
 
-pub fn part_a(input: &str) -> i64 {
-    let (wire1, wire2) = input.trim().split_once('\n').unwrap();
-    common_points(&make_path(wire1), &make_path(wire2))
+pub fn part_a(input: &str) -> Result<i64, SolutionError> {
+    let (wire1, wire2) = parse_wires(input)?;
+    common_points(&wire1, &wire2)
         .iter()
         .map(|&pos| V2(0, 0).manhattan_distance(pos))
         .min()
-        .unwrap()
+        .ok_or_else(|| SolutionError::no_answer("wires never cross"))
 }
 
-pub fn part_b(input: &str) -> usize {
-    let (wire1, wire2) = input.trim().split_once('\n').unwrap();
-    let (wire1, wire2) = (make_path(wire1), make_path(wire2));
+pub fn part_b(input: &str) -> Result<usize, SolutionError> {
+    let (wire1, wire2) = parse_wires(input)?;
+    // every common point is on both wires, so both positions are always found
+    let steps = |wire: &[V2], pos| {
+        wire.iter()
+            .position(|&p| p == pos)
+            .expect("a common point is on both wires")
+    };
     common_points(&wire1, &wire2)
         .iter()
-        .map(|&pos| {
-            wire1.iter().position(|&p| p == pos).unwrap()
-                + wire2.iter().position(|&p| p == pos).unwrap()
-                + 2
-        })
+        .map(|&pos| steps(&wire1, pos) + steps(&wire2, pos) + 2)
         .min()
-        .unwrap()
+        .ok_or_else(|| SolutionError::no_answer("wires never cross"))
 }
 
-fn common_points(wire1: &Vec<V2>, wire2: &Vec<V2>) -> HashSet<V2> {
+fn common_points(wire1: &[V2], wire2: &[V2]) -> HashSet<V2> {
     let set1: HashSet<_> = wire1.iter().collect();
     let set2: HashSet<_> = wire2.iter().collect();
     set1.intersection(&set2).map(|p| **p).collect()
 }
 
-fn make_path(path: &str) -> Vec<V2> {
+fn parse_wires(text: &str) -> Result<(Vec<V2>, Vec<V2>), ParseError> {
+    let input = Input::new(text);
+    let (wire1, wire2) = input.split_once(text.trim(), "\n")?;
+    Ok((make_path(&input, wire1)?, make_path(&input, wire2)?))
+}
+
+fn make_path(input: &Input, path: &str) -> Result<Vec<V2>, ParseError> {
     let mut position = V2(0, 0);
     let mut result = Vec::new();
-    for line in path.split(',') {
-        let direction = match line.as_bytes()[0] {
-            b'R' => V2(0, 1),
-            b'L' => V2(0, -1),
-            b'U' => V2(1, 0),
-            b'D' => V2(-1, 0),
-            _ => unreachable!(),
+    for step in path.trim().split(',') {
+        let mut chars = step.chars();
+        let direction = match chars.next() {
+            Some('R') => V2(0, 1),
+            Some('L') => V2(0, -1),
+            Some('U') => V2(1, 0),
+            Some('D') => V2(-1, 0),
+            _ => return Err(input.error(step, "expected one of `R`, `L`, `U` or `D`")),
         };
-        for _ in 0..line[1..].parse::<u64>().unwrap() {
+        for _ in 0..input.number::<u64>(chars.as_str())? {
             position += direction;
             result.push(position);
         }
     }
-    result
+    Ok(result)
+}
+
+utils::solution! {
+    Day03 = 3;
+    a: |input| part_a(input),
+    b: |input| part_b(input),
 }
 
 #[cfg(test)]
@@ -62,20 +80,33 @@ U98,R91,D20,R16,D67,R40,U7,R15,U6,R7";
 
     #[test]
     fn example_a() {
-        assert_eq!(part_a(EXAMPLE_1), 159);
-        assert_eq!(part_a(EXAMPLE_2), 135)
+        assert_eq!(part_a(EXAMPLE_1), Ok(159));
+        assert_eq!(part_a(EXAMPLE_2), Ok(135))
     }
 
     #[test]
     fn example_b() {
-        assert_eq!(part_b(EXAMPLE_1), 610);
-        assert_eq!(part_b(EXAMPLE_2), 410);
+        assert_eq!(part_b(EXAMPLE_1), Ok(610));
+        assert_eq!(part_b(EXAMPLE_2), Ok(410));
     }
 
     #[test]
     fn real() {
         let input = include_str!("input.txt");
-        assert_eq!(part_a(input), 1195);
-        assert_eq!(part_b(input), 91518);
+        assert_eq!(part_a(input), Ok(1195));
+        assert_eq!(part_b(input), Ok(91518));
+    }
+
+    #[test]
+    fn bad_input() {
+        let Err(SolutionError::Parse(error)) = part_a("R8,U5\nU7,X6,D4") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.excerpt, "U7,X6,D4");
+        assert_eq!(
+            part_b("R8,U5\nL7,D6"),
+            Err(SolutionError::no_answer("wires never cross"))
+        );
     }
 }
@@ -1,13 +1,19 @@
-pub fn part_a(input: &str) -> usize {
-    let (lo, hi) = input.split_once('-').unwrap();
-    let (lo, hi) = (lo.parse::<u64>().unwrap(), hi.parse::<u64>().unwrap());
-    (lo..=hi).filter(|&n| matches_criteria1(n)).count()
+use utils::parse::{Input, ParseError};
+
+pub fn part_a(input: &str) -> Result<usize, ParseError> {
+    let (lo, hi) = parse_range(input)?;
+    Ok((lo..=hi).filter(|&n| matches_criteria1(n)).count())
+}
+
+pub fn part_b(input: &str) -> Result<usize, ParseError> {
+    let (lo, hi) = parse_range(input)?;
+    Ok((lo..=hi).filter(|&n| matches_criteria2(n)).count())
 }
 
-pub fn part_b(input: &str) -> usize {
-    let (lo, hi) = input.split_once('-').unwrap();
-    let (lo, hi) = (lo.parse::<u64>().unwrap(), hi.parse::<u64>().unwrap());
-    (lo..=hi).filter(|&n| matches_criteria2(n)).count()
+fn parse_range(text: &str) -> Result<(u64, u64), ParseError> {
+    let input = Input::new(text);
+    let (lo, hi) = input.split_once(text.trim(), "-")?;
+    Ok((input.number(lo)?, input.number(hi)?))
 }
 
 fn matches_criteria1(n: u64) -> bool {
@@ -26,7 +32,7 @@ fn group_of_two(digits: &[u8]) -> bool {
     (0..(digits.len() - 1)).any(|i| {
         digits[i] == digits[i + 1]
             && (i == 0 || digits[i - 1] != digits[i])
-            && digits.get(i + 2).map_or(true, |&a| a != digits[i])
+            && digits.get(i + 2).is_none_or(|&a| a != digits[i])
     })
 }
 
@@ -40,12 +46,16 @@ fn digits(mut n: u64) -> Vec<u8> {
     result
 }
 
+utils::solution! {
+    Day04 = 4;
+    a: |input| Ok(part_a(input)?),
+    b: |input| Ok(part_b(input)?),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const REAL_INPUT: &str = "278384-824795";
-
     #[test]
     fn matches_criteria1_test() {
         assert!(matches_criteria1(111111));
@@ -62,7 +72,9 @@ mod tests {
 
     #[test]
     fn real() {
-        assert_eq!(part_a(REAL_INPUT), 921);
-        assert_eq!(part_b(REAL_INPUT), 603);
+        let input = include_str!("input.txt");
+        assert_eq!(part_a(input), Ok(921));
+        assert_eq!(part_b(input), Ok(603));
+        assert_eq!(part_a("278384").unwrap_err().message, "expected `-`");
     }
 }
@@ -12,6 +12,8 @@ pub fn part_b(input: &str) -> i64 {
     computer.output[computer.output.len() - 1]
 }
 
+utils::solution!(Day05 = 5, check IntcodeComputer::try_parse_program);
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -1,3 +1,5 @@
+use utils::solution::SolutionError;
+
 pub fn part_a(input: &str) -> i64 {
     let orbits = parse_orbits(input);
     let mut to_process = vec![("COM", 0)];
@@ -39,6 +41,13 @@ fn parse_orbits(input: &str) -> Vec<(&str, &str)> {
         .collect()
 }
 
+utils::solution! {
+    Day06 = 6;
+    a: |input| Ok(part_a(input)),
+    b: |input| part_b(input)
+        .ok_or_else(|| SolutionError::no_answer("YOU and SAN don't orbit anything in common")),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
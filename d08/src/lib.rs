@@ -1,3 +1,9 @@
+use utils::{grid::Grid, ocr, solution::SolutionError};
+
+// The size of the picture in the puzzle itself, rather than the examples
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
 pub fn part_a(width: usize, height: usize, input: &str) -> usize {
     let count = |c1, layer: &[u8]| layer.iter().filter(|&&c2| c1 == c2).count();
     input
@@ -8,20 +14,26 @@ pub fn part_a(width: usize, height: usize, input: &str) -> usize {
         .unwrap()
 }
 
-pub fn part_b(width: usize, height: usize, input: &str) -> String {
+pub fn part_b(width: usize, height: usize, input: &str) -> Result<String, SolutionError> {
     let layers: Vec<_> = input.as_bytes().chunks(width * height).collect();
-    (0..width * height)
+    // the first pixel in each position that isn't transparent
+    let pixels = (0..width * height)
         .map(|i| {
             layers
                 .iter()
                 .map(|layer| layer[i])
                 .find(|&ch| ch != b'2')
-                .map(|ch| if ch == b'0' { " " } else { "#" })
                 .unwrap()
-                .to_owned()
-                + (if (i + 1) % width == 0 { "\n" } else { "" })
+                == b'1'
         })
-        .collect::<String>()
+        .collect();
+    ocr::read_answer(&Grid::from_vec(width, height, pixels))
+}
+
+utils::solution! {
+    Day08 = 8;
+    a: |input| Ok(part_a(WIDTH, HEIGHT, input)),
+    b: |input| part_b(WIDTH, HEIGHT, input),
 }
 
 #[cfg(test)]
@@ -32,7 +44,14 @@ mod tests {
     fn real() {
         let input = include_str!("input.txt");
         assert_eq!(part_a(25, 6, input), 0);
-        let output = include_str!("output.txt");
-        assert_eq!(part_b(25, 6, input), output);
+        assert_eq!(part_b(25, 6, input).as_deref(), Ok("LBRCE"));
+    }
+
+    #[test]
+    fn unreadable() {
+        assert!(matches!(
+            part_b(2, 2, "0110"),
+            Err(SolutionError::NoAnswer(_))
+        ));
     }
 }
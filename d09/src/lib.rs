@@ -12,6 +12,8 @@ pub fn part_b(input: &str) -> i64 {
     computer.output[0]
 }
 
+utils::solution!(Day09 = 9, check IntcodeComputer::try_parse_program);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,7 +51,8 @@ fn nth_destroyed(station: V2, n: usize, mut others: Vec<V2>) -> Option<V2> {
 fn count_detectable_asteroids(station: V2, asteroids: &[V2]) -> usize {
     asteroids
         .iter()
-        .filter_map(|&asteroid| (station != asteroid).then(|| (asteroid - station).simplify()))
+        .filter(|&&asteroid| asteroid != station)
+        .map(|&asteroid| (asteroid - station).simplify())
         .collect::<HashSet<_>>()
         .len()
 }
@@ -68,16 +69,18 @@ struct Wrap(V2);
 
 impl PartialOrd for Wrap {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.0.arg().partial_cmp(&other.0.arg())
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Wrap {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.0.arg().total_cmp(&other.0.arg())
     }
 }
 
+utils::solution!(Day10 = 10);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,32 +1,26 @@
-use utils::v3::V3;
+use utils::{
+    cycle::brent,
+    parse::{Input, ParseError},
+    v3::V3,
+};
 
-pub fn part_a(input: &str) -> i64 {
-    let mut moons: Vec<_> = input.lines().map(parse_line).collect();
+pub fn part_a(input: &str) -> Result<i64, ParseError> {
+    let mut moons = parse(input)?;
     for _ in 0..1000 {
         tick(&mut moons);
     }
-    moons.iter().map(Moon::total_energy).sum()
+    Ok(moons.iter().map(Moon::total_energy).sum())
 }
 
-pub fn part_b(input: &str) -> i64 {
-    let mut moons: Vec<_> = input.lines().map(parse_line).collect();
-    let axes: Vec<_> = (0..=2).map(|n| get_axis(&moons, n)).collect();
-    let mut repetitions = [None; 3];
-    let mut n = 0;
-    while repetitions.iter().any(Option::is_none) {
-        n += 1;
-        tick(&mut moons);
-        for i in 0..=2 {
-            if repetitions[i].is_none() && get_axis(&moons, i) == axes[i] {
-                repetitions[i] = Some(n);
-            }
-        }
-    }
-    repetitions
-        .iter()
-        .map(|n| n.unwrap())
-        .reduce(utils::lcm)
-        .unwrap()
+pub fn part_b(input: &str) -> Result<i64, ParseError> {
+    let moons = parse(input)?;
+    // The axes don't affect each other, so the whole system repeats once they've all lined up
+    Ok((0..=2)
+        .map(|axis| {
+            let cycle = brent(get_axis(&moons, axis), |state: &Vec<_>| tick_axis(state));
+            cycle.length as i64
+        })
+        .fold(1, utils::lcm))
 }
 
 #[derive(Eq, PartialEq, Clone, Copy)]
@@ -45,11 +39,11 @@ impl Moon {
     }
 
     const fn potential_energy(&self) -> i64 {
-        self.position.0.abs() + self.position.1.abs() + self.position.2.abs()
+        self.position.manhattan()
     }
 
     const fn kinetic_energy(&self) -> i64 {
-        self.velocity.0.abs() + self.velocity.1.abs() + self.velocity.2.abs()
+        self.velocity.manhattan()
     }
 
     const fn total_energy(&self) -> i64 {
@@ -57,20 +51,28 @@ impl Moon {
     }
 }
 
-fn parse_line(line: &str) -> Moon {
-    let line = &line[1..(line.len() - 1)];
-    let pos = match line
-        .split(", ")
-        .map(|dim| dim[2..].parse::<i64>().unwrap())
-        .collect::<Vec<_>>()[..]
-    {
-        [x, y, z] => V3(x, y, z),
-        _ => unreachable!(),
-    };
-    Moon {
-        position: pos,
-        velocity: V3(0, 0, 0),
+fn parse(text: &str) -> Result<Vec<Moon>, ParseError> {
+    let input = Input::new(text);
+    let moons: Vec<Moon> = input
+        .lines()
+        .map(|line| parse_line(&input, line))
+        .collect::<Result<_, _>>()?;
+    if moons.is_empty() {
+        return Err(input.error(text, "expected some moons"));
     }
+    Ok(moons)
+}
+
+fn parse_line<'a>(input: &Input<'a>, line: &'a str) -> Result<Moon, ParseError> {
+    let inner = input.bracketed(line, '<', '>')?;
+    let position = match input.key_values(inner)?[..] {
+        [("x", x), ("y", y), ("z", z)] => V3(x, y, z),
+        _ => return Err(input.error(inner, "expected `x=.., y=.., z=..`")),
+    };
+    Ok(Moon {
+        position,
+        velocity: V3::default(),
+    })
 }
 
 fn tick(moons: &mut [Moon]) {
@@ -88,19 +90,32 @@ fn tick(moons: &mut [Moon]) {
     }
 }
 
+// The same as `tick`, for the (position, velocity) pairs along one axis
+fn tick_axis(axis: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    axis.iter()
+        .map(|&(position, velocity)| {
+            let pull: i64 = axis
+                .iter()
+                .map(|(other, _)| (other - position).signum())
+                .sum();
+            (position + velocity + pull, velocity + pull)
+        })
+        .collect()
+}
+
 fn get_axis(moons: &[Moon], axis: usize) -> Vec<(i64, i64)> {
-    let f: fn(V3) -> i64 = match axis {
-        0 => |v| v.0,
-        1 => |v| v.1,
-        2 => |v| v.2,
-        _ => unreachable!(),
-    };
     moons
         .iter()
-        .map(|moon| (f(moon.position), f(moon.velocity)))
+        .map(|moon| (moon.position[axis], moon.velocity[axis]))
         .collect()
 }
 
+utils::solution! {
+    Day12 = 12;
+    a: |input| Ok(part_a(input)?),
+    b: |input| Ok(part_b(input)?),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,13 +126,23 @@ mod tests {
 <x=2, y=-10, z=-7>
 <x=4, y=-8, z=8>
 <x=3, y=5, z=-1>";
-        assert_eq!(part_b(input), 2772);
+        assert_eq!(part_b(input), Ok(2772));
     }
 
     #[test]
     fn real() {
         let input = include_str!("input.txt");
-        assert_eq!(part_a(input), 7077);
-        assert_eq!(part_b(input), 402_951_477_454_512);
+        assert_eq!(part_a(input), Ok(7077));
+        assert_eq!(part_b(input), Ok(402_951_477_454_512));
+    }
+
+    #[test]
+    fn bad_input() {
+        let error = part_a("<x=1, y=2, z=3>\n<x=1, y=2>").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "expected `x=.., y=.., z=..`");
+        let error = part_a("<x=1, y=2, z=3\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 15));
+        assert_eq!(part_b("\n").unwrap_err().message, "expected some moons");
     }
 }
@@ -1,6 +1,14 @@
 use std::collections::HashMap;
 
 use intcode::IntcodeComputer;
+use utils::{
+    coords::RowCol,
+    render::{densify, to_text, Orientation, Palette, BLACK, WHITE},
+    v2::V2,
+};
+
+// The game reports the score as if it were drawn at x = -1
+const SCORE: RowCol = RowCol::from_screen_xy(-1, 0);
 
 pub fn part_a(input: &str) -> usize {
     get_screen(&mut IntcodeComputer::run_program(
@@ -20,23 +28,45 @@ pub fn part_b(input: &str) -> i64 {
         computer.run_until_needs_input();
         let screen = get_screen(&mut computer);
         if computer.halted {
-            return screen[&(-1, 0)];
+            return screen[&SCORE];
         }
         let ball_pos = screen.iter().find(|&(_k, &v)| v == 4).unwrap().0;
         let paddle_pos = screen.iter().find(|&(_k, &v)| v == 3).unwrap().0;
-        let joystick_position = (ball_pos.0 - paddle_pos.0).signum();
+        let joystick_position = (ball_pos.col - paddle_pos.col).signum();
         computer.add_input(joystick_position);
     }
 }
 
-fn get_screen(computer: &mut IntcodeComputer) -> HashMap<(i64, i64), i64> {
+// What the game looks like before it starts
+pub fn screenshot(input: &str) -> String {
+    let program = IntcodeComputer::parse_program(input);
+    let screen = get_screen(&mut IntcodeComputer::run_program(program, None));
+    let tiles = screen
+        .into_iter()
+        .filter(|&(pos, _)| pos != SCORE)
+        .map(|(pos, tile)| (V2::from(pos), tile))
+        .collect();
+    let palette = Palette::new(' ', BLACK)
+        .with(1, '#', (128, 128, 128))
+        .with(2, '=', (0, 160, 255))
+        .with(3, '-', WHITE)
+        .with(4, 'o', WHITE);
+    to_text(&densify(&tiles, 0, Orientation::Screen), &palette)
+}
+
+fn get_screen(computer: &mut IntcodeComputer) -> HashMap<RowCol, i64> {
     computer
         .output
         .chunks(3)
-        .map(|instruction| ((instruction[0], instruction[1]), instruction[2]))
+        .map(|instruction| {
+            let pos = RowCol::from_screen_xy(instruction[0], instruction[1]);
+            (pos, instruction[2])
+        })
         .collect()
 }
 
+utils::solution!(Day13 = 13, check IntcodeComputer::try_parse_program);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -47,4 +77,13 @@ mod tests {
         assert_eq!(part_a(input), 265);
         assert_eq!(part_b(input), 13331);
     }
+
+    #[test]
+    fn screenshot_test() {
+        let input = include_str!("input.txt");
+        let picture = screenshot(input);
+        assert_eq!(picture.matches('=').count(), 265);
+        assert_eq!(picture.matches('o').count(), 1);
+        assert!(picture.lines().next().unwrap().chars().all(|c| c == '#'));
+    }
 }
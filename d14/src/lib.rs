@@ -1,36 +1,23 @@
 use std::collections::HashMap;
 
+use utils::{
+    bisect::largest_within_budget,
+    parse::{Input, ParseError},
+    solution::SolutionError,
+};
+
 type Reactions<'a> = HashMap<&'a str, (i64, Vec<(&'a str, i64)>)>;
 
-pub fn part_a(input: &str) -> i64 {
-    ore_needed(&parse(input), 1)
-}
+const ORE: i64 = 1_000_000_000_000;
 
-pub fn part_b(input: &str) -> i64 {
-    // binary search
-    let reactions = parse(input);
-    let target = 1_000_000_000_000;
-    let (mut low, mut high) = find_bounds(&reactions, target);
-    loop {
-        let mid = (low + high) / 2;
-        let ore = ore_needed(&reactions, mid);
-        if low >= (high - 1) || ore == target {
-            return mid;
-        } else if ore < target {
-            low = mid;
-        } else if ore > target {
-            high = mid;
-        }
-    }
+pub fn part_a(input: &str) -> Result<i64, ParseError> {
+    Ok(ore_needed(&parse(input)?, 1))
 }
 
-fn find_bounds(reactions: &Reactions, target: i64) -> (i64, i64) {
-    let low = target / ore_needed(reactions, 1);
-    let mut high = low * 10;
-    while ore_needed(reactions, high) < target {
-        high *= 10;
-    }
-    (low, high)
+pub fn part_b(input: &str) -> Result<i64, SolutionError> {
+    let reactions = parse(input)?;
+    largest_within_budget(1, ORE, |fuel| ore_needed(&reactions, fuel))
+        .ok_or_else(|| SolutionError::no_answer("not enough ore for one fuel"))
 }
 
 fn ore_needed(reactions: &Reactions, amount: i64) -> i64 {
@@ -63,21 +50,31 @@ fn chemicals_needed<'a>(
     needed
 }
 
-fn parse(input: &str) -> Reactions {
+fn parse(text: &str) -> Result<Reactions<'_>, ParseError> {
+    let input = Input::new(text);
     input
         .lines()
         .map(|line| {
-            let (inputs, output) = line.split_once(" => ").unwrap();
-            let (out_chemical, out_amount) = parse_element(output);
-            let inputs = inputs.split(", ").map(parse_element).collect();
-            (out_chemical, (out_amount, inputs))
+            let (inputs, output) = input.split_once(line, " => ")?;
+            let (out_chemical, out_amount) = parse_element(&input, output)?;
+            let inputs = inputs
+                .split(", ")
+                .map(|element| parse_element(&input, element))
+                .collect::<Result<_, _>>()?;
+            Ok((out_chemical, (out_amount, inputs)))
         })
         .collect()
 }
 
-fn parse_element(s: &str) -> (&str, i64) {
-    let (amount, chemical) = s.split_once(' ').unwrap();
-    (chemical, amount.parse().unwrap())
+fn parse_element<'a>(input: &Input<'a>, s: &'a str) -> Result<(&'a str, i64), ParseError> {
+    let (amount, chemical) = input.split_once(s.trim(), " ")?;
+    Ok((chemical, input.number(amount)?))
+}
+
+utils::solution! {
+    Day14 = 14;
+    a: |input| Ok(part_a(input)?),
+    b: |input| part_b(input),
 }
 
 #[cfg(test)]
@@ -93,13 +90,21 @@ mod tests {
 5 B, 7 C => 1 BC
 4 C, 1 A => 1 CA
 2 AB, 3 BC, 4 CA => 1 FUEL";
-        assert_eq!(part_a(input), 165);
+        assert_eq!(part_a(input), Ok(165));
     }
 
     #[test]
     fn test() {
         let input = include_str!("input.txt");
-        assert_eq!(part_a(input), 1582325);
-        assert_eq!(part_b(input), 2267486);
+        assert_eq!(part_a(input), Ok(1582325));
+        assert_eq!(part_b(input), Ok(2267486));
+    }
+
+    #[test]
+    fn too_little_ore() {
+        assert_eq!(
+            part_b("1000000000001 ORE => 1 FUEL"),
+            Err(SolutionError::no_answer("not enough ore for one fuel"))
+        );
     }
 }
@@ -1,6 +1,8 @@
-use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 use intcode::IntcodeComputer;
+use utils::bfs::{bfs, flood_fill_time};
+use utils::{direction::Direction, v2::V2};
 
 pub fn part_a(input: &str) -> i64 {
     let program = IntcodeComputer::parse_program(input);
@@ -16,40 +18,27 @@ pub fn part_b(input: &str) -> i64 {
     time_oxygen_spread(&map)
 }
 
-// a breadth-first traversal of the given input space, labelling the oxygen
-// system as 0, and then adding 1 as the oxygen travels outwards
+// how long it takes for oxygen to spread from the oxygen system to every open space
 fn time_oxygen_spread(map: &HashMap<(i64, i64), i64>) -> i64 {
     let oxygen_system = *map.iter().find(|&(_k, &v)| v == 2).unwrap().0;
-    let mut oxygen_spread = HashMap::new();
-    let mut queue = VecDeque::from([(oxygen_system, 0)]);
-    while let Some((pos, elapsed_time)) = queue.pop_front() {
-        oxygen_spread.insert(pos, elapsed_time);
-        for new_pos in taxicab_neighbours(pos) {
-            if !oxygen_spread.contains_key(&new_pos) && matches!(map.get(&new_pos), Some(1 | 2)) {
-                queue.push_back((new_pos, elapsed_time + 1));
-            }
-        }
-    }
-    *oxygen_spread.values().max().unwrap()
+    flood_fill_time(|&pos| open_neighbours(map, pos), [oxygen_system]).unwrap() as i64
 }
 
 // finds the shortest path to the oxygen system, via a breadth-first search
 fn shortest_path(map: &HashMap<(i64, i64), i64>) -> Option<i64> {
-    let start = (0, 0);
-    let mut queue: VecDeque<((i64, i64), i64)> = VecDeque::from([(start, 0)]);
-    let mut visited: HashSet<(i64, i64)> = HashSet::from([start]);
-    while let Some((pos, path_length)) = queue.pop_front() {
-        if map[&pos] == 2 {
-            return Some(path_length);
-        }
-        visited.insert(pos);
-        for new_pos in taxicab_neighbours(pos) {
-            if !visited.contains(&new_pos) && matches!(map.get(&new_pos), Some(1 | 2)) {
-                queue.push_back((new_pos, path_length + 1));
-            }
-        }
-    }
-    None
+    let path = bfs(
+        |&pos| open_neighbours(map, pos),
+        |pos| map[pos] == 2,
+        (0, 0),
+    )?;
+    Some(path.len() as i64 - 1)
+}
+
+fn open_neighbours(map: &HashMap<(i64, i64), i64>, pos: (i64, i64)) -> Vec<(i64, i64)> {
+    taxicab_neighbours(pos)
+        .into_iter()
+        .filter(|new_pos| matches!(map.get(new_pos), Some(1 | 2)))
+        .collect()
 }
 
 fn taxicab_neighbours(pos: (i64, i64)) -> Vec<(i64, i64)> {
@@ -67,22 +56,17 @@ fn explore(comp: &mut IntcodeComputer) -> HashMap<(i64, i64), i64> {
     let mut current_pos = (0, 0);
     let mut fully_explored: HashSet<(i64, i64)> = HashSet::new();
     let mut result: HashMap<(i64, i64), i64> = HashMap::from([(current_pos, 1)]);
-    let mut predecessor: HashMap<(i64, i64), ((i64, i64), i64)> = HashMap::new();
+    let mut predecessor: HashMap<(i64, i64), ((i64, i64), Direction)> = HashMap::new();
     loop {
         let mut any_new = false;
-        for dir in 1..=4 {
-            let new_pos = match dir {
-                1 => (current_pos.0 + 1, current_pos.1),
-                2 => (current_pos.0 - 1, current_pos.1),
-                3 => (current_pos.0, current_pos.1 - 1),
-                4 => (current_pos.0, current_pos.1 + 1),
-                _ => unreachable!(),
-            };
+        for dir in Direction::all() {
+            let V2(dy, dx) = dir.to_v2();
+            let new_pos = (current_pos.0 + dy, current_pos.1 + dx);
             if let Entry::Vacant(e) = result.entry(new_pos) {
                 let response = make_move(comp, dir);
                 e.insert(response);
                 if response != 0 {
-                    predecessor.insert(new_pos, (current_pos, opposite_direction(dir)));
+                    predecessor.insert(new_pos, (current_pos, dir.turn_around()));
                     current_pos = new_pos;
                     any_new = true;
                 }
@@ -103,22 +87,24 @@ fn explore(comp: &mut IntcodeComputer) -> HashMap<(i64, i64), i64> {
     }
 }
 
-fn opposite_direction(i: i64) -> i64 {
-    match i {
-        1 => 2,
-        2 => 1,
-        3 => 4,
-        4 => 3,
-        _ => unreachable!(),
+// The codes the repair droid uses for each direction
+const fn movement_command(dir: Direction) -> i64 {
+    match dir {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
     }
 }
 
-fn make_move(comp: &mut IntcodeComputer, dir: i64) -> i64 {
-    comp.add_input(dir);
+fn make_move(comp: &mut IntcodeComputer, dir: Direction) -> i64 {
+    comp.add_input(movement_command(dir));
     comp.run_until_needs_input();
     comp.output.pop().unwrap()
 }
 
+utils::solution!(Day15 = 15, check IntcodeComputer::try_parse_program);
+
 #[cfg(test)]
 mod tests {
     use super::*;
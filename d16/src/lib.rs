@@ -72,6 +72,8 @@ fn make_pattern_matrix(size: usize) -> Vec<Vec<i64>> {
     result
 }
 
+utils::solution!(Day16 = 16);
+
 #[cfg(test)]
 mod tests {
     use super::*;
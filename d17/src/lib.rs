@@ -1,17 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
 use intcode::IntcodeComputer;
-use utils::v2::V2;
+use utils::{direction::Direction, v2::V2};
 
 type Position = V2;
-type Direction = V2;
 type Map = HashMap<Position, i64>;
 
-const LEFT: Direction = V2(0, -1);
-const RIGHT: Direction = V2(0, 1);
-const UP: Direction = V2(-1, 0);
-const DOWN: Direction = V2(1, 0);
-
 #[derive(Debug)]
 pub enum Instruction {
     Forward(i64),
@@ -55,20 +49,20 @@ pub fn part_b(input: &str) -> i64 {
 
 pub fn find_path(map: &Map) -> Vec<Instruction> {
     let (mut position, mut direction) = find_robot(map);
-    let is_available_position = |pos: V2, diff: V2| {
-        map.get(&(pos + diff))
+    let is_available_position = |pos: V2, direction: Direction| {
+        map.get(&(pos + direction.to_v2()))
             .filter(|&&v| is_scaffold(v))
             .is_some()
     };
     let mut result = Vec::new();
     loop {
-        let left_turn = left_of(direction);
-        let right_turn = right_of(direction);
+        let left_turn = direction.turn_left();
+        let right_turn = direction.turn_right();
         if is_available_position(position, direction) {
             let mut n = 0;
             while is_available_position(position, direction) {
                 n += 1;
-                position += direction;
+                position += direction.to_v2();
             }
             result.push(Instruction::Forward(n));
         } else if is_available_position(position, left_turn) {
@@ -87,36 +81,10 @@ const fn is_scaffold(space: i64) -> bool {
     space != 87 && space != 46
 }
 
-fn right_of(dir: Direction) -> V2 {
-    match dir {
-        LEFT => UP,
-        RIGHT => DOWN,
-        UP => RIGHT,
-        DOWN => LEFT,
-        _ => unreachable!(),
-    }
-}
-
-fn left_of(dir: Direction) -> V2 {
-    match dir {
-        LEFT => DOWN,
-        RIGHT => UP,
-        UP => LEFT,
-        DOWN => RIGHT,
-        _ => unreachable!(),
-    }
-}
-
 // Finds the starting position and direction of the robot
 fn find_robot(map: &Map) -> (Position, Direction) {
     map.iter()
-        .find_map(|(&k, v)| match v {
-            60 => Some((k, LEFT)),
-            62 => Some((k, RIGHT)),
-            94 => Some((k, UP)),
-            118 => Some((k, DOWN)),
-            _ => None,
-        })
+        .find_map(|(&k, &v)| Some((k, Direction::from_char(char::from(v as u8))?)))
         .unwrap_or_else(|| panic!("No robot found: {:?}", map.values().collect::<HashSet<_>>()))
 }
 
@@ -133,6 +101,8 @@ pub fn make_map(program: Vec<i64>) -> Map {
         .collect()
 }
 
+utils::solution!(Day17 = 17, check IntcodeComputer::try_parse_program);
+
 #[cfg(test)]
 mod tests {
     use super::*;
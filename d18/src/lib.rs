@@ -2,9 +2,11 @@
 // They do things mostly the same as I tried, but I'm not entirely sure why theirs
 // works and mine didn't.
 
-use std::cmp::Ordering;
-use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
+use utils::corridors::contract_grid;
+use utils::dijkstra::{dijkstra, dijkstra_distances};
 use utils::v2::V2;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -32,61 +34,23 @@ const fn parse_tile(c: char) -> Tile {
 }
 
 fn build_graph(grid: &Grid) -> Graph {
-    let mut graph = HashMap::new();
-    for (coord, tile) in grid {
-        if let Tile::Node(c) = tile {
-            graph.insert(*c, reachable_from(grid, *coord));
-        }
-    }
-    graph
-}
-
-fn reachable_from(grid: &Grid, start: V2) -> HashMap<char, usize> {
-    let mut visited = HashSet::from([start]);
-    let mut result = HashMap::new();
-    let mut queue = VecDeque::from([(start, 0)]);
-    while let Some((current_pos, steps)) = queue.pop_front() {
-        for neighbour in &current_pos.taxicab_neighbours() {
-            if let Some(tile) = grid.get(neighbour) {
-                if visited.contains(neighbour) {
-                    continue;
-                }
-                visited.insert(*neighbour);
-                match tile {
-                    Tile::Blank => {
-                        queue.push_back((*neighbour, steps + 1));
-                    }
-                    Tile::Node(c) => {
-                        result.insert(*c, steps + 1);
-                    }
-                    Tile::Wall => {}
-                }
-            }
-        }
-    }
-    result
-}
-
-#[derive(PartialEq, Eq)]
-struct State {
-    steps: usize,
-    node: char,
-    keys: BTreeSet<char>,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .steps
-            .cmp(&self.steps)
-            .then(self.keys.len().cmp(&other.keys.len()))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+    let name = |pos: &V2| match grid[pos] {
+        Tile::Node(c) => c,
+        _ => unreachable!(),
+    };
+    let nodes: HashSet<V2> = grid
+        .iter()
+        .filter(|(_, tile)| matches!(tile, Tile::Node(_)))
+        .map(|(&pos, _)| pos)
+        .collect();
+    let passable = |pos: &V2| matches!(grid.get(pos), Some(Tile::Blank | Tile::Node(_)));
+    contract_grid(passable, &nodes)
+        .into_iter()
+        .map(|(from, edges)| {
+            let edges = edges.iter().map(|(to, &steps)| (name(to), steps)).collect();
+            (name(&from), edges)
+        })
+        .collect()
 }
 
 fn count_keys(graph: &Graph) -> usize {
@@ -95,94 +59,43 @@ fn count_keys(graph: &Graph) -> usize {
 
 fn search(graph: &Graph) -> Option<usize> {
     let key_count = count_keys(graph);
-    let mut priority_queue = BinaryHeap::from([State {
-        steps: 0,
-        node: '@',
-        keys: BTreeSet::new(),
-    }]);
-    let mut best_costs = HashMap::from([(('@', BTreeSet::new()), 0)]);
-    let mut cache = HashMap::new();
-    while let Some(current) = priority_queue.pop() {
-        if current.keys.len() == key_count {
-            return Some(current.steps);
-        }
-        let next_options = cache
-            .entry((current.node, current.keys.clone()))
-            .or_insert_with(|| search_keys(graph, &current.keys, current.node));
-        for &(next_node, cost) in next_options.iter() {
-            let mut next_keys = current.keys.clone();
-            next_keys.insert(next_node);
-            let next_steps = current.steps + cost;
-            let previous_shortest = best_costs
-                .entry((next_node, next_keys.clone()))
-                .or_insert(usize::max_value());
-            if next_steps < *previous_shortest {
-                *previous_shortest = next_steps;
-                priority_queue.push(State {
-                    steps: next_steps,
-                    node: next_node,
-                    keys: next_keys,
-                });
-            }
-        }
-    }
-    None
-}
-
-#[derive(PartialEq, Eq)]
-struct DijkstraState {
-    cost: usize,
-    node: char,
-}
-
-impl Ord for DijkstraState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.node.cmp(&other.node))
-    }
-}
-
-impl PartialOrd for DijkstraState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+    let neighbours = |(node, keys): &(char, BTreeSet<char>)| {
+        search_keys(graph, keys, *node)
+            .into_iter()
+            .map(|(next_node, cost)| {
+                let mut next_keys = keys.clone();
+                next_keys.insert(next_node);
+                ((next_node, next_keys), cost)
+            })
+            .collect()
+    };
+    let done = |(_, keys): &(char, BTreeSet<char>)| keys.len() == key_count;
+    dijkstra(neighbours, done, ('@', BTreeSet::new())).map(|(_, steps)| steps)
 }
 
-// adapted from https://doc.rust-lang.org/std/collections/binary_heap/index.html
 // dijkstra search for reachable new keys from start node
 fn search_keys(graph: &Graph, keys: &BTreeSet<char>, start: char) -> Vec<(char, usize)> {
-    // dist[node] = current shortest distance from `start` to `node`
-    let mut dist = HashMap::from([(start, 0)]);
-    let mut heap = BinaryHeap::from([DijkstraState {
-        cost: 0,
-        node: start,
-    }]);
-    let mut reach = HashSet::new();
-    while let Some(DijkstraState { cost, node }) = heap.pop() {
-        if node.is_lowercase() && !keys.contains(&node) {
-            reach.insert(node);
-            continue;
+    let is_new_key = |node: char| node.is_lowercase() && !keys.contains(&node);
+    let neighbours = |&node: &char| {
+        if is_new_key(node) {
+            // stop at the first new key along each route
+            return Vec::new();
         }
-        for (&next_node, &next_cost) in &graph[&node] {
-            if next_node.is_uppercase() && !keys.contains(&next_node.to_ascii_lowercase()) {
-                continue;
-            }
-            let next = DijkstraState {
-                cost: cost + next_cost,
-                node: next_node,
-            };
-            if !matches!(dist.get(&next.node), Some(&d) if next.cost >= d) {
-                dist.insert(next.node, next.cost);
-                heap.push(next);
-            }
-        }
-    }
-    reach.into_iter().map(|node| (node, dist[&node])).collect()
-}
-
-pub fn part_1(input: &str) -> usize {
+        graph[&node]
+            .iter()
+            .filter(|(next_node, _)| {
+                !next_node.is_uppercase() || keys.contains(&next_node.to_ascii_lowercase())
+            })
+            .map(|(&next_node, &cost)| (next_node, cost))
+            .collect()
+    };
+    dijkstra_distances(neighbours, start)
+        .into_iter()
+        .filter(|&(node, _)| is_new_key(node))
+        .collect()
+}
+
+pub fn part_a(input: &str) -> usize {
     let grid = parse(input);
     let graph = build_graph(&grid);
     search(&graph).unwrap()
@@ -209,73 +122,38 @@ fn four_robots(grid: &mut HashMap<V2, Tile>) {
 fn search_four(graph: &Graph) -> Option<usize> {
     let key_count = count_keys(graph);
     let robots = ['@', '=', '%', '$'];
-    let mut priority_queue = BinaryHeap::from([FourState {
-        steps: 0,
-        robots,
-        keys: BTreeSet::new(),
-    }]);
-    let mut best_costs = HashMap::from([((robots, BTreeSet::new()), 0)]);
-    let mut cache = HashMap::new();
-    while let Some(current) = priority_queue.pop() {
-        if current.keys.len() == key_count {
-            return Some(current.steps);
-        }
-        for (robot_number, &robot_location) in current.robots.iter().enumerate() {
+    let cache = RefCell::new(HashMap::new());
+    let neighbours = |(robots, keys): &([char; 4], BTreeSet<char>)| {
+        let mut result = Vec::new();
+        for (robot_number, &robot_location) in robots.iter().enumerate() {
             let next_options = cache
-                .entry((robot_location, current.keys.clone()))
-                .or_insert_with(|| search_keys(graph, &current.keys, robot_location));
-            for &(next_node, cost) in next_options.iter() {
-                let mut next_keys = current.keys.clone();
+                .borrow_mut()
+                .entry((robot_location, keys.clone()))
+                .or_insert_with(|| search_keys(graph, keys, robot_location))
+                .clone();
+            for (next_node, cost) in next_options {
+                let mut next_keys = keys.clone();
                 next_keys.insert(next_node);
-                let mut next_robots = current.robots;
+                let mut next_robots = *robots;
                 next_robots[robot_number] = next_node;
-                let next_steps = current.steps + cost;
-                let previous_shortest = best_costs
-                    .entry((next_robots, next_keys.clone()))
-                    .or_insert(usize::max_value());
-                if next_steps < *previous_shortest {
-                    *previous_shortest = next_steps;
-                    priority_queue.push(FourState {
-                        steps: next_steps,
-                        robots: next_robots,
-                        keys: next_keys,
-                    });
-                }
+                result.push(((next_robots, next_keys), cost));
             }
         }
-    }
-    None
-}
-
-#[derive(PartialEq, Eq)]
-struct FourState {
-    steps: usize,
-    robots: [char; 4],
-    keys: BTreeSet<char>,
+        result
+    };
+    let done = |(_, keys): &([char; 4], BTreeSet<char>)| keys.len() == key_count;
+    dijkstra(neighbours, done, (robots, BTreeSet::new())).map(|(_, steps)| steps)
 }
 
-impl Ord for FourState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .steps
-            .cmp(&self.steps)
-            .then(self.keys.len().cmp(&other.keys.len()))
-    }
-}
-
-impl PartialOrd for FourState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-pub fn part_2(input: &str) -> usize {
+pub fn part_b(input: &str) -> usize {
     let mut grid = parse(input);
     four_robots(&mut grid);
     let graph = build_graph(&grid);
     search_four(&graph).unwrap()
 }
 
+utils::solution!(Day18 = 18);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -284,13 +162,13 @@ mod test {
     fn first() {
         let input = include_str!("input.txt");
         let expected = 4544;
-        assert_eq!(part_1(input), expected);
+        assert_eq!(part_a(input), expected);
     }
 
     #[test]
     fn second() {
         let input = include_str!("input.txt");
         let expected = 1692;
-        assert_eq!(part_2(input), expected);
+        assert_eq!(part_b(input), expected);
     }
 }
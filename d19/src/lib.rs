@@ -35,6 +35,8 @@ impl Drone {
     }
 }
 
+utils::solution!(Day19 = 19, check IntcodeComputer::try_parse_program);
+
 #[cfg(test)]
 mod tests {
     use super::*;
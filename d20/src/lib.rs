@@ -13,6 +13,8 @@ pub fn part_b(input: &str) -> usize {
     graph.solve().unwrap()
 }
 
+utils::solution!(Day20 = 20);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,10 +2,10 @@
 
 use std::{
     cmp,
-    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    collections::{HashMap, HashSet},
 };
 
-use utils::v2::V2;
+use utils::{corridors::contract_grid, dijkstra::dijkstra, v2::V2};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Graph {
@@ -22,9 +22,7 @@ impl Graph {
             edges: HashMap::new(),
         };
         let nodes = graph.discover_nodes(&maze);
-        for &node in &nodes {
-            graph.explore_out(node, &nodes, &maze);
-        }
+        graph.explore_corridors(&nodes, &maze);
         graph
     }
 
@@ -45,17 +43,10 @@ impl Graph {
         nodes
     }
 
-    fn explore_out(&mut self, start: V2, nodes: &HashSet<V2>, maze: &Maze) {
-        let mut visited = HashSet::from([start]);
-        let mut queue = VecDeque::from([(start, 0)]);
-        while let Some((pos, distance)) = queue.pop_front() {
-            if nodes.contains(&pos) && pos != start {
-                self.add_edge(start, pos, distance);
-            }
-            for neighbour in pos.taxicab_neighbours() {
-                if maze.has_point(&neighbour) && visited.insert(neighbour) {
-                    queue.push_back((neighbour, distance + 1));
-                }
+    fn explore_corridors(&mut self, nodes: &HashSet<V2>, maze: &Maze) {
+        for (from, edges) in contract_grid(|pos| maze.has_point(pos), nodes) {
+            for (to, distance) in edges {
+                self.add_edge(from, to, distance);
             }
         }
     }
@@ -70,25 +61,14 @@ impl Graph {
     }
 
     pub fn solve(&self) -> Option<usize> {
-        let mut to_visit = BinaryHeap::from([State::new(self.start, 0)]);
-        let mut distances = HashMap::from([(self.start, 0)]);
-        let mut visited = HashSet::new();
-        while let Some(State { position, distance }) = to_visit.pop() {
-            if !visited.insert(position) {
-                continue;
-            }
-            for (neighbour, cost) in &self.edges[&position] {
-                let new_distance = distance + cost;
-                let is_shorter = distances
-                    .get(neighbour)
-                    .map_or(true, |&current| new_distance < current);
-                if is_shorter {
-                    distances.insert(*neighbour, new_distance);
-                    to_visit.push(State::new(*neighbour, new_distance));
-                }
-            }
-        }
-        distances.get(&self.end).copied()
+        let neighbours = |position: &V2| {
+            self.edges[position]
+                .iter()
+                .map(|(&neighbour, &cost)| (neighbour, cost))
+                .collect()
+        };
+        dijkstra(neighbours, |&position| position == self.end, self.start)
+            .map(|(_, distance)| distance)
     }
 }
 
@@ -133,38 +113,6 @@ impl Maze {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct State {
-    position: V2,
-    distance: usize,
-}
-
-impl State {
-    fn new(position: V2, distance: usize) -> Self {
-        Self { position, distance }
-    }
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.distance.cmp(&self.distance)
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl PartialEq for State {
-    fn eq(&self, other: &Self) -> bool {
-        self.distance.eq(&other.distance)
-    }
-}
-
-impl Eq for State {}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,21 +136,6 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
-    #[test]
-    fn priority_queue_test() {
-        let s1 = State {
-            position: V2(16, 13),
-            distance: 26,
-        };
-        let s2 = State {
-            position: V2(12, 11),
-            distance: 30,
-        };
-        let mut priority_queue = BinaryHeap::from([s1, s2]);
-        assert_eq!(priority_queue.pop(), Some(s1));
-        assert_eq!(priority_queue.pop(), Some(s2));
-    }
-
     #[test]
     fn idempotence() {
         let input = include_str!("input.txt");
@@ -1,8 +1,8 @@
 // The version where labels lead to recursive copies of the maze, aka part 2.
 
-use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, HashSet};
 
-use utils::v2::V2;
+use utils::{corridors::contract_grid, dijkstra::dijkstra, v2::V2};
 
 pub struct Graph {
     start: V2,
@@ -18,9 +18,7 @@ impl Graph {
             edges: HashMap::new(),
         };
         let nodes = graph.discover_nodes(&maze);
-        for &node in &nodes {
-            graph.explore_out(node, &nodes, &maze);
-        }
+        graph.explore_corridors(&nodes, &maze);
         graph
     }
 
@@ -46,17 +44,10 @@ impl Graph {
         labels
     }
 
-    fn explore_out(&mut self, start: V2, labels: &HashSet<V2>, maze: &Maze) {
-        let mut visited = HashSet::from([start]);
-        let mut queue = VecDeque::from([(start, 0)]);
-        while let Some((pos, distance)) = queue.pop_front() {
-            if labels.contains(&pos) && pos != start {
-                self.add_edge(start, pos, distance, DepthChange::NoChange);
-            }
-            for neighbour in pos.taxicab_neighbours() {
-                if maze.has_point(&neighbour) && visited.insert(neighbour) {
-                    queue.push_back((neighbour, distance + 1));
-                }
+    fn explore_corridors(&mut self, nodes: &HashSet<V2>, maze: &Maze) {
+        for (from, edges) in contract_grid(|pos| maze.has_point(pos), nodes) {
+            for (to, distance) in edges {
+                self.add_edge(from, to, distance, DepthChange::NoChange);
             }
         }
     }
@@ -69,23 +60,17 @@ impl Graph {
     }
 
     pub fn solve(&self) -> Option<usize> {
-        let mut to_visit = BinaryHeap::from([State(self.start, 0, 0)]);
-        let mut visited = HashSet::new();
-        while let Some(State(position, distance, depth)) = to_visit.pop() {
-            if !visited.insert((position, depth)) {
-                continue;
-            } else if depth == 0 && position == self.end {
-                return Some(distance);
-            }
-            for (&neighbour, (cost, change)) in &self.edges[&position] {
-                let next_depth = depth + change.amount_changed();
-                let next = State(neighbour, distance + cost, next_depth);
-                if next_depth >= 0 {
-                    to_visit.push(next);
-                }
-            }
-        }
-        None
+        let neighbours = |&(position, depth): &(V2, i32)| {
+            self.edges[&position]
+                .iter()
+                .map(|(&neighbour, (cost, change))| {
+                    ((neighbour, depth + change.amount_changed()), *cost)
+                })
+                .filter(|&((_, next_depth), _)| next_depth >= 0)
+                .collect()
+        };
+        let done = |&(position, depth): &(V2, i32)| depth == 0 && position == self.end;
+        dijkstra(neighbours, done, (self.start, 0)).map(|(_, distance)| distance)
     }
 }
 
@@ -163,28 +148,6 @@ impl Maze {
     }
 }
 
-struct State(V2, usize, i32);
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.1.cmp(&self.1)
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl PartialEq for State {
-    fn eq(&self, other: &Self) -> bool {
-        self.1.eq(&other.1)
-    }
-}
-
-impl Eq for State {}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,5 @@
 use intcode::IntcodeComputer;
+use utils::solution::SolutionError;
 
 pub fn part_a(input: &str) -> Result<i64, String> {
     let jumpscript_program = "OR A T
@@ -34,8 +35,7 @@ RUN
 
 fn run_jumpscript(intcode: Vec<i64>, jumpscript: &str) -> Result<i64, String> {
     let mut computer = IntcodeComputer::new(intcode, None);
-    let mut program = jumpscript.chars().map(from_ascii);
-    while let Some(i) = program.next() {
+    for i in jumpscript.chars().map(from_ascii) {
         computer.run_until_needs_input();
         computer.add_input(i);
     }
@@ -58,6 +58,12 @@ pub fn from_ascii(c: char) -> i64 {
     c as i64
 }
 
+utils::solution! {
+    Day21 = 21, check IntcodeComputer::try_parse_program;
+    a: |input| part_a(input).map_err(SolutionError::NoAnswer),
+    b: |input| part_b(input).map_err(SolutionError::NoAnswer),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
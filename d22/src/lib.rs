@@ -1,6 +1,6 @@
 use std::num::ParseIntError;
 
-use modexp::{modexp, BigInt};
+use utils::math::{modinv, modpow, mulmod};
 
 pub fn part_a(input: &str) -> isize {
     parse(input)
@@ -9,35 +9,21 @@ pub fn part_a(input: &str) -> isize {
         .fold(2019, |pos, shuffle| shuffle.perform(pos, 10_007))
 }
 
-// Adapted from https://github.com/tginsberg/advent-2019-kotlin/blob/master/src/main/kotlin/com/ginsberg/advent2019/Day22.kt
-// Don't really understand it, but then, neither did they
-pub fn part_b(input: &str) -> BigInt {
-    let num_cards: BigInt = 119315717514047_isize.into();
-    let num_shuffles: BigInt = 101741582076661_isize.into();
-    let mut memory0: BigInt = 1.into();
-    let mut memory1: BigInt = 0.into();
-    let find = 2020;
-    for instruction in parse(input).unwrap().iter().rev() {
-        match instruction {
-            Shuffle::Cut(n) => {
-                memory1 = memory1 + n;
-            }
-            Shuffle::Increment(n) => {
-                let t = BigInt::from(*n).modpow(&(&num_cards - 2), &num_cards);
-                memory0 = memory0 * &t;
-                memory1 = memory1 * t;
-            }
-            Shuffle::NewStack => {
-                memory0 = -memory0;
-                memory1 = (memory1 + 1) * -1;
-            }
-        }
-    }
-    let power = memory0.modpow(&num_shuffles, &num_cards);
-    ((&power * find)
-        + ((memory1 * (power + &num_cards - 1))
-            * modexp(memory0 - 1, &num_cards - 2, num_cards.clone())))
-        % num_cards
+// Every shuffle moves the card at position x to a * x + b, for some a and b, and so does any
+// combination of them, so the whole process can be done with modular arithmetic instead.
+pub fn part_b(input: &str) -> i64 {
+    let num_cards = 119_315_717_514_047;
+    let num_shuffles = 101_741_582_076_661;
+    let (a, b) = parse(input)
+        .unwrap()
+        .iter()
+        .fold((1, 0), |(a, b), shuffle| shuffle.compose(a, b, num_cards));
+    // repeating x -> a * x + b k times gives a^k * x + b * (a^k - 1) / (a - 1)
+    let a_k = modpow(a, num_shuffles, num_cards);
+    let geometric_sum = mulmod(a_k - 1, modinv(a - 1, num_cards).unwrap(), num_cards);
+    let b_k = mulmod(b, geometric_sum, num_cards);
+    // then run it backwards to find the card that ends up at 2020
+    mulmod(2020 - b_k, modinv(a_k, num_cards).unwrap(), num_cards)
 }
 
 fn parse(input: &str) -> Result<Vec<Shuffle>, ParseError> {
@@ -61,6 +47,16 @@ impl Shuffle {
             Shuffle::Increment(n) => (pos * n) % size,
         }
     }
+
+    /// Given a shuffle that moves the card at `x` to `a * x + b`, returns the (a, b) that does
+    /// the same, followed by this one.
+    fn compose(&self, a: i64, b: i64, size: i64) -> (i64, i64) {
+        match *self {
+            Shuffle::NewStack => ((-a).rem_euclid(size), (-b - 1).rem_euclid(size)),
+            Shuffle::Cut(n) => (a, (b - n as i64).rem_euclid(size)),
+            Shuffle::Increment(n) => (mulmod(a, n as i64, size), mulmod(b, n as i64, size)),
+        }
+    }
 }
 
 impl TryFrom<&str> for Shuffle {
@@ -90,6 +86,8 @@ impl From<ParseIntError> for ParseError {
     }
 }
 
+utils::solution!(Day22 = 22);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,8 +115,6 @@ deal into new stack";
     #[test]
     fn part_b_test() {
         let input = include_str!("input.txt");
-        let result = part_b(input);
-        println!("{}", result);
-        assert_eq!(result, 1041334417227_isize.into());
+        assert_eq!(part_b(input), 1041334417227);
     }
 }
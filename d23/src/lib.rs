@@ -81,6 +81,8 @@ impl Network {
     }
 }
 
+utils::solution!(Day23 = 23, check IntcodeComputer::try_parse_program);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,5 +5,15 @@ pub use part_a::part_a;
 pub use part_b::part_b;
 
 fn bug_rules(alive: bool, num_neighbours: usize) -> bool {
-    (alive && num_neighbours == 1) || (!alive && (num_neighbours == 1 || num_neighbours == 2))
+    if alive {
+        num_neighbours == 1
+    } else {
+        num_neighbours == 1 || num_neighbours == 2
+    }
+}
+
+utils::solution! {
+    Day24 = 24;
+    a: |input| Ok(part_a(input)?),
+    b: |input| Ok(part_b(input)),
 }
@@ -1,43 +1,24 @@
-use std::collections::HashSet;
+use utils::{bitgrid::BitGrid, cycle::find_cycle, parse::ParseError};
 
-use utils::v2::V2;
-
-use crate::bug_rules;
-
-pub fn part_a(input: &str) -> usize {
-    let mut grid = parse(input);
-    let mut seen = HashSet::new();
-    loop {
-        let rating = biodiversity_rating(&grid);
-        if !seen.insert(rating) {
-            return rating;
-        }
-        grid = tick(grid);
-    }
-}
-
-fn biodiversity_rating(grid: &HashSet<V2>) -> usize {
-    grid.iter().map(|V2(y, x)| 1 << (5 * y + x)).sum()
+pub fn part_a(input: &str) -> Result<usize, ParseError> {
+    // the layout that appears twice first is the one at the start of the cycle
+    let (cycle, layouts) = find_cycle(parse(input)?, tick);
+    Ok(biodiversity_rating(&layouts[cycle.start]))
 }
 
-fn tick(grid: HashSet<V2>) -> HashSet<V2> {
-    (0..5)
-        .flat_map(|y| (0..5).map(move |x| V2(y, x)))
-        .filter(|pos: &V2| bug_rules(grid.contains(&pos), neighbours(&grid, *pos)))
-        .collect()
+// Each cell is worth 2^n, where n is its position in reading order, which is how BitGrid stores
+// them anyway
+fn biodiversity_rating(grid: &BitGrid) -> usize {
+    grid.bits() as usize
 }
 
-fn neighbours(grid: &HashSet<V2>, pos: V2) -> usize {
-    pos.taxicab_neighbours()
-        .into_iter()
-        .filter(|neighbour| grid.contains(&neighbour))
-        .count()
+// The same as `bug_rules`, for every cell at once
+fn tick(grid: &BitGrid) -> BitGrid {
+    grid.neighbours_exactly(1) | (!*grid & grid.neighbours_exactly(2))
 }
 
-fn parse(input: &str) -> HashSet<V2> {
-    utils::parse_grid(input)
-        .filter_map(|(pos, c)| (c == '#').then_some(pos))
-        .collect()
+fn parse(input: &str) -> Result<BitGrid, ParseError> {
+    BitGrid::parse(input.trim(), '#')
 }
 
 #[cfg(test)]
@@ -51,7 +32,7 @@ mod tests {
 .....
 #....
 .#...";
-        let input = parse(text);
+        let input = parse(text).unwrap();
         assert_eq!(biodiversity_rating(&input), 2129920);
     }
 
@@ -62,12 +43,12 @@ mod tests {
 #..##
 ..#..
 #....";
-        assert_eq!(part_a(text), 2129920);
+        assert_eq!(part_a(text), Ok(2129920));
     }
 
     #[test]
     fn real() {
         let input = include_str!("input.txt");
-        assert_eq!(part_a(input), 20751345);
+        assert_eq!(part_a(input), Ok(20751345));
     }
 }
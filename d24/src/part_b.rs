@@ -1,9 +1,12 @@
 use std::collections::HashSet;
 
-use utils::v2::V2;
+use utils::{v2::V2, vecn::VecN};
 
 use crate::bug_rules;
 
+// A position on one of the recursive grids, as (depth, y, x)
+pub type Point = VecN<3>;
+
 pub fn part_b(input: &str) -> usize {
     let mut grid = parse(input);
     for _ in 0..200 {
@@ -33,31 +36,31 @@ fn alive_neighbours(grid: &HashSet<Point>, p: Point) -> usize {
 
 fn neighbours(p: Point) -> HashSet<Point> {
     let mut result = HashSet::new();
-    let depth = p.depth;
-    for V2(y, x) in V2(p.y, p.x).taxicab_neighbours() {
+    let VecN([depth, py, px]) = p;
+    for V2(y, x) in V2(py, px).taxicab_neighbours() {
         if (y, x) != (2, 2) && (x >= 0) && (y >= 0) && (x <= 4) && (y <= 4) {
-            result.insert(Point::new(depth, y, x));
+            result.insert(VecN([depth, y, x]));
             continue;
         }
         if (y, x) == (2, 2) {
-            let news = (0..5).map(|i| match (p.y, p.x) {
-                (1, 2) => Point::new(depth + 1, 0, i),
-                (2, 1) => Point::new(depth + 1, i, 0),
-                (3, 2) => Point::new(depth + 1, 4, i),
-                (2, 3) => Point::new(depth + 1, i, 4),
+            let news = (0..5).map(|i| match (py, px) {
+                (1, 2) => VecN([depth + 1, 0, i]),
+                (2, 1) => VecN([depth + 1, i, 0]),
+                (3, 2) => VecN([depth + 1, 4, i]),
+                (2, 3) => VecN([depth + 1, i, 4]),
                 _ => unreachable!(),
             });
             result.extend(news);
         }
         if x < 0 {
-            result.insert(Point::new(p.depth - 1, 2, 1));
+            result.insert(VecN([depth - 1, 2, 1]));
         } else if x > 4 {
-            result.insert(Point::new(p.depth - 1, 2, 3));
+            result.insert(VecN([depth - 1, 2, 3]));
         }
         if y < 0 {
-            result.insert(Point::new(p.depth - 1, 1, 2));
+            result.insert(VecN([depth - 1, 1, 2]));
         } else if y > 4 {
-            result.insert(Point::new(p.depth - 1, 3, 2));
+            result.insert(VecN([depth - 1, 3, 2]));
         }
     }
     result
@@ -66,23 +69,10 @@ fn neighbours(p: Point) -> HashSet<Point> {
 pub fn parse(input: &str) -> HashSet<Point> {
     utils::parse_grid(input)
         .filter(|&(_, c)| c == '#')
-        .map(|(V2(y, x), _)| Point::new(0, y, x))
+        .map(|(V2(y, x), _)| VecN([0, y, x]))
         .collect()
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-pub struct Point {
-    depth: i64,
-    y: i64,
-    x: i64,
-}
-
-impl Point {
-    fn new(depth: i64, y: i64, x: i64) -> Self {
-        Self { depth, y, x }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,55 +80,55 @@ mod tests {
     #[test]
     fn adjacency_rules() {
         let expected = HashSet::from([
-            Point::new(0, 4, 3),
-            Point::new(0, 2, 3),
-            Point::new(0, 3, 2),
-            Point::new(0, 3, 4),
+            VecN([0, 4, 3]),
+            VecN([0, 2, 3]),
+            VecN([0, 3, 2]),
+            VecN([0, 3, 4]),
         ]);
-        assert_eq!(neighbours(Point::new(0, 3, 3)), expected);
+        assert_eq!(neighbours(VecN([0, 3, 3])), expected);
         let expected = HashSet::from([
-            Point::new(1, 2, 1),
-            Point::new(1, 0, 1),
-            Point::new(1, 1, 0),
-            Point::new(1, 1, 2),
+            VecN([1, 2, 1]),
+            VecN([1, 0, 1]),
+            VecN([1, 1, 0]),
+            VecN([1, 1, 2]),
         ]);
-        assert_eq!(neighbours(Point::new(1, 1, 1)), expected);
+        assert_eq!(neighbours(VecN([1, 1, 1])), expected);
         let expected = HashSet::from([
-            Point::new(1, 0, 4),
-            Point::new(1, 0, 2),
-            Point::new(1, 1, 3),
-            Point::new(0, 1, 2),
+            VecN([1, 0, 4]),
+            VecN([1, 0, 2]),
+            VecN([1, 1, 3]),
+            VecN([0, 1, 2]),
         ]);
-        assert_eq!(neighbours(Point::new(1, 0, 3)), expected);
+        assert_eq!(neighbours(VecN([1, 0, 3])), expected);
         let expected = HashSet::from([
-            Point::new(1, 0, 3),
-            Point::new(1, 1, 4),
-            Point::new(0, 1, 2),
-            Point::new(0, 2, 3),
+            VecN([1, 0, 3]),
+            VecN([1, 1, 4]),
+            VecN([0, 1, 2]),
+            VecN([0, 2, 3]),
         ]);
-        assert_eq!(neighbours(Point::new(1, 0, 4)), expected);
+        assert_eq!(neighbours(VecN([1, 0, 4])), expected);
         let expected = HashSet::from([
-            Point::new(0, 1, 3),
-            Point::new(0, 3, 3),
-            Point::new(0, 2, 4),
-            Point::new(1, 0, 4),
-            Point::new(1, 1, 4),
-            Point::new(1, 2, 4),
-            Point::new(1, 3, 4),
-            Point::new(1, 4, 4),
+            VecN([0, 1, 3]),
+            VecN([0, 3, 3]),
+            VecN([0, 2, 4]),
+            VecN([1, 0, 4]),
+            VecN([1, 1, 4]),
+            VecN([1, 2, 4]),
+            VecN([1, 3, 4]),
+            VecN([1, 4, 4]),
         ]);
-        assert_eq!(neighbours(Point::new(0, 2, 3)), expected);
+        assert_eq!(neighbours(VecN([0, 2, 3])), expected);
         let expected = HashSet::from([
-            Point::new(1, 1, 3),
-            Point::new(1, 3, 3),
-            Point::new(1, 2, 4),
-            Point::new(2, 0, 4),
-            Point::new(2, 1, 4),
-            Point::new(2, 2, 4),
-            Point::new(2, 3, 4),
-            Point::new(2, 4, 4),
+            VecN([1, 1, 3]),
+            VecN([1, 3, 3]),
+            VecN([1, 2, 4]),
+            VecN([2, 0, 4]),
+            VecN([2, 1, 4]),
+            VecN([2, 2, 4]),
+            VecN([2, 3, 4]),
+            VecN([2, 4, 4]),
         ]);
-        assert_eq!(neighbours(Point::new(1, 2, 3)), expected);
+        assert_eq!(neighbours(VecN([1, 2, 3])), expected);
     }
 
     #[test]
@@ -0,0 +1,28 @@
+use std::fmt::Display;
+
+use utils::solution::{Part, Solution, SolutionError};
+
+// The last day is a text adventure, played by hand with `cargo run -p d25`, so there's nothing
+// here to run
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const PARTS: &'static [Part] = &[];
+
+    fn parse(_input: &str) -> Result<Self, SolutionError> {
+        Ok(Self)
+    }
+
+    fn part_a(&self) -> Result<impl Display, SolutionError> {
+        Err::<String, _>(SolutionError::no_answer(
+            "day 25 is played by hand, with `cargo run -p d25`",
+        ))
+    }
+
+    fn part_b(&self) -> Result<impl Display, SolutionError> {
+        Err::<String, _>(SolutionError::no_answer(
+            "there's no second puzzle on the last day",
+        ))
+    }
+}
@@ -0,0 +1,193 @@
+// Records which instructions a computer executed, so we can see what parts of a program a given
+// set of inputs exercised. Coverage from several runs of the same program can be merged.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::disassembler::{disassemble, Line};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Coverage {
+    // How many times the instruction at each address was executed
+    pub counts: BTreeMap<usize, usize>,
+}
+
+impl Coverage {
+    pub fn record(&mut self, pc: usize) {
+        *self.counts.entry(pc).or_insert(0) += 1;
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (&pc, &count) in &other.counts {
+            *self.counts.entry(pc).or_insert(0) += count;
+        }
+    }
+
+    pub fn count(&self, pc: usize) -> usize {
+        *self.counts.get(&pc).unwrap_or(&0)
+    }
+
+    // Splits the program into basic blocks and marks which of them were executed
+    pub fn report(&self, program: &[i64]) -> CoverageReport {
+        let lines = disassemble(program);
+        let mut leaders: BTreeSet<usize> =
+            lines.iter().filter_map(Line::static_jump_target).collect();
+        leaders.insert(0);
+        let mut blocks: Vec<BasicBlock> = Vec::new();
+        let mut block_ended = true;
+        for line in lines {
+            let is_instruction = matches!(line, Line::Instruction { .. });
+            if is_instruction && (block_ended || leaders.contains(&line.address())) {
+                blocks.push(BasicBlock {
+                    count: 0,
+                    lines: Vec::new(),
+                });
+            }
+            block_ended = match &line {
+                Line::Instruction { op, .. } => op.ends_block(),
+                Line::Data { .. } => true,
+            };
+            if is_instruction {
+                blocks.last_mut().unwrap().lines.push(line);
+            }
+        }
+        // a jump into the middle of an instruction runs code the disassembler never saw, so this
+        // looks at every address in the block, not just where it starts
+        for block in &mut blocks {
+            block.count = self
+                .counts
+                .range(block.start()..block.end())
+                .map(|(_, &count)| count)
+                .max()
+                .unwrap_or(0);
+        }
+        CoverageReport { blocks }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BasicBlock {
+    // The most times any address in the block was executed
+    pub count: usize,
+    pub lines: Vec<Line>,
+}
+
+impl BasicBlock {
+    pub fn start(&self) -> usize {
+        self.lines[0].address()
+    }
+
+    // One past the last address in the block
+    pub fn end(&self) -> usize {
+        let last = self.lines.last().unwrap();
+        last.address() + last.num_words()
+    }
+
+    pub const fn is_covered(&self) -> bool {
+        self.count > 0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoverageReport {
+    pub blocks: Vec<BasicBlock>,
+}
+
+impl CoverageReport {
+    pub fn covered(&self) -> impl Iterator<Item = &BasicBlock> {
+        self.blocks.iter().filter(|b| b.is_covered())
+    }
+
+    pub fn uncovered(&self) -> impl Iterator<Item = &BasicBlock> {
+        self.blocks.iter().filter(|b| !b.is_covered())
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}/{} blocks covered",
+            self.covered().count(),
+            self.blocks.len()
+        )?;
+        for block in &self.blocks {
+            if block.is_covered() {
+                writeln!(
+                    f,
+                    "block {}..{}: {}x",
+                    block.start(),
+                    block.end(),
+                    block.count
+                )?;
+            } else {
+                writeln!(f, "block {}..{}: not covered", block.start(), block.end())?;
+            }
+            for line in &block.lines {
+                writeln!(f, "{}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntcodeComputer;
+
+    // Outputs 0 unless the input is 8
+    const PROGRAM: [i64; 13] = [3, 12, 1008, 12, 8, 12, 1005, 12, 11, 104, 0, 99, 0];
+
+    fn run(input: i64) -> Coverage {
+        let mut computer = IntcodeComputer::new(PROGRAM.to_vec(), Some(vec![input]));
+        computer.collect_coverage();
+        computer.run();
+        computer.coverage.unwrap()
+    }
+
+    #[test]
+    fn basic_blocks() {
+        let report = run(7).report(&PROGRAM);
+        let ranges: Vec<_> = report.blocks.iter().map(|b| (b.start(), b.end())).collect();
+        assert_eq!(ranges, vec![(0, 9), (9, 11), (11, 12)]);
+    }
+
+    #[test]
+    fn merge() {
+        let mut coverage = run(7);
+        assert_eq!(coverage.report(&PROGRAM).uncovered().count(), 0);
+        let other = run(8);
+        coverage.merge(&other);
+        assert_eq!(coverage.count(0), 2);
+        assert_eq!(coverage.count(9), 1);
+    }
+
+    #[test]
+    fn uncovered() {
+        let report = run(8).report(&PROGRAM);
+        let uncovered: Vec<_> = report.uncovered().map(BasicBlock::start).collect();
+        assert_eq!(uncovered, vec![9]);
+        assert!(report
+            .to_string()
+            .contains("block 9..11: not covered\n     9: out #0\n"));
+    }
+
+    #[test]
+    fn jump_into_an_instruction() {
+        // jumps to the parameters of the add, which run as `out #7` then `halt`
+        let program = [1105, 1, 4, 1101, 104, 7, 99];
+        let mut computer = IntcodeComputer::new(program.to_vec(), None);
+        computer.collect_coverage();
+        computer.run();
+        assert_eq!(computer.output, vec![7]);
+        let report = computer.coverage.unwrap().report(&program);
+        let blocks: Vec<_> = report
+            .blocks
+            .iter()
+            .map(|b| (b.start(), b.end(), b.count))
+            .collect();
+        assert_eq!(blocks, vec![(0, 3, 1), (3, 7, 1)]);
+        assert_eq!(report.uncovered().count(), 0);
+    }
+}
@@ -0,0 +1,153 @@
+// Virtual hardware that can be mapped onto a range of an intcode computer's memory. Reads and
+// writes to that range go to the device rather than to `data`, with addresses given relative to
+// the start of the range.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt;
+use std::ops::Range;
+
+pub trait Device: Any + fmt::Debug {
+    // How many addresses the device answers to. Offsets passed to `read` and `write` are always
+    // less than this.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn read(&self, offset: usize) -> i64;
+
+    fn write(&mut self, offset: usize, value: i64);
+
+    // Called once for every instruction the computer executes
+    fn tick(&mut self) {}
+
+    // Everything that affects what the device does next, so cycle detection can tell whether the
+    // computer has really come back to the same state
+    fn state(&self) -> Vec<i64>;
+}
+
+#[derive(Debug)]
+pub struct MappedDevice {
+    pub range: Range<usize>,
+    pub device: Box<dyn Device>,
+}
+
+// A grid of pixels, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Framebuffer {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<i64>,
+}
+
+impl Framebuffer {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    pub fn pixel(&self, y: usize, x: usize) -> i64 {
+        self.pixels[y * self.width + x]
+    }
+}
+
+impl Device for Framebuffer {
+    fn len(&self) -> usize {
+        self.pixels.len()
+    }
+
+    fn read(&self, offset: usize) -> i64 {
+        self.pixels[offset]
+    }
+
+    fn write(&mut self, offset: usize, value: i64) {
+        self.pixels[offset] = value;
+    }
+
+    fn state(&self) -> Vec<i64> {
+        self.pixels.clone()
+    }
+}
+
+impl fmt::Display for Framebuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.pixels.chunks(self.width) {
+            for &pixel in row {
+                write!(f, "{}", if pixel == 0 { '.' } else { '#' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Reads give the number of instructions executed since it was mapped. Writing sets the count.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Clock {
+    pub ticks: i64,
+}
+
+impl Device for Clock {
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn read(&self, _offset: usize) -> i64 {
+        self.ticks
+    }
+
+    fn write(&mut self, _offset: usize, value: i64) {
+        self.ticks = value;
+    }
+
+    fn tick(&mut self) {
+        self.ticks += 1;
+    }
+
+    fn state(&self) -> Vec<i64> {
+        vec![self.ticks]
+    }
+}
+
+// A xorshift random number generator. Every read gives a new non-negative number, and writing
+// reseeds it.
+#[derive(Debug, Clone)]
+pub struct Random {
+    state: Cell<u64>,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Cell::new(seed.max(1)),
+        }
+    }
+}
+
+impl Device for Random {
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn read(&self, _offset: usize) -> i64 {
+        let mut x = self.state.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state.set(x);
+        (x >> 1) as i64
+    }
+
+    fn write(&mut self, _offset: usize, value: i64) {
+        self.state.set((value as u64).max(1));
+    }
+
+    fn state(&self) -> Vec<i64> {
+        vec![self.state.get() as i64]
+    }
+}
@@ -0,0 +1,210 @@
+// Turns an intcode program back into something readable. Decoding is a linear sweep from address
+// 0, so anything that doesn't decode as an instruction is shown as a single word of data.
+
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Mult,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustRelativeBase,
+    Halt,
+}
+
+impl Op {
+    const fn from_opcode(opcode: i64) -> Option<Self> {
+        Some(match opcode % 100 {
+            1 => Self::Add,
+            2 => Self::Mult,
+            3 => Self::Input,
+            4 => Self::Output,
+            5 => Self::JumpIfTrue,
+            6 => Self::JumpIfFalse,
+            7 => Self::LessThan,
+            8 => Self::Equals,
+            9 => Self::AdjustRelativeBase,
+            99 => Self::Halt,
+            _ => return None,
+        })
+    }
+
+    const fn num_params(self) -> usize {
+        match self {
+            Self::Add | Self::Mult | Self::LessThan | Self::Equals => 3,
+            Self::JumpIfTrue | Self::JumpIfFalse => 2,
+            Self::Input | Self::Output | Self::AdjustRelativeBase => 1,
+            Self::Halt => 0,
+        }
+    }
+
+    // Whether execution might not carry on to the next instruction in memory
+    pub const fn ends_block(self) -> bool {
+        matches!(self, Self::JumpIfTrue | Self::JumpIfFalse | Self::Halt)
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Mult => "mul",
+            Self::Input => "in",
+            Self::Output => "out",
+            Self::JumpIfTrue => "jnz",
+            Self::JumpIfFalse => "jz",
+            Self::LessThan => "lt",
+            Self::Equals => "eq",
+            Self::AdjustRelativeBase => "arb",
+            Self::Halt => "halt",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Param {
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Position(n) => write!(f, "[{}]", n),
+            Self::Immediate(n) => write!(f, "#{}", n),
+            Self::Relative(n) => write!(f, "[rb{:+}]", n),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Line {
+    Instruction {
+        address: usize,
+        op: Op,
+        params: Vec<Param>,
+    },
+    Data {
+        address: usize,
+        value: i64,
+    },
+}
+
+impl Line {
+    pub const fn address(&self) -> usize {
+        match self {
+            Self::Instruction { address, .. } | Self::Data { address, .. } => *address,
+        }
+    }
+
+    // The number of memory cells the line takes up
+    pub fn num_words(&self) -> usize {
+        match self {
+            Self::Instruction { params, .. } => params.len() + 1,
+            Self::Data { .. } => 1,
+        }
+    }
+
+    // Where a jump instruction goes, if that can be known without running the program
+    pub fn static_jump_target(&self) -> Option<usize> {
+        match self {
+            Self::Instruction {
+                op: Op::JumpIfTrue | Op::JumpIfFalse,
+                params,
+                ..
+            } => match params[1] {
+                Param::Immediate(n) => usize::try_from(n).ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Instruction {
+                address,
+                op,
+                params,
+            } => {
+                write!(f, "{:>6}: {}", address, op.name())?;
+                for (i, param) in params.iter().enumerate() {
+                    write!(f, "{}{}", if i == 0 { " " } else { ", " }, param)?;
+                }
+                Ok(())
+            }
+            Self::Data { address, value } => write!(f, "{:>6}: data {}", address, value),
+        }
+    }
+}
+
+fn decode(program: &[i64], address: usize) -> Option<Line> {
+    let opcode = program[address];
+    let op = Op::from_opcode(opcode)?;
+    let params = (1..=op.num_params())
+        .map(|position| {
+            let value = *program.get(address + position)?;
+            match (opcode / 10i64.pow(position as u32 + 1)) % 10 {
+                0 => Some(Param::Position(value)),
+                1 => Some(Param::Immediate(value)),
+                2 => Some(Param::Relative(value)),
+                _ => None,
+            }
+        })
+        .collect::<Option<_>>()?;
+    Some(Line::Instruction {
+        address,
+        op,
+        params,
+    })
+}
+
+pub fn disassemble(program: &[i64]) -> Vec<Line> {
+    let mut result = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        let line = decode(program, address).unwrap_or(Line::Data {
+            address,
+            value: program[address],
+        });
+        address += line.num_words();
+        result.push(line);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disassemble_test() {
+        let program = vec![1101, 46, 1, 7, 204, -1, 1105, 1, 0, 99, 12345];
+        let text: Vec<_> = disassemble(&program)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let expected = [
+            "     0: add #46, #1, [7]",
+            "     4: out [rb-1]",
+            "     6: jnz #1, #0",
+            "     9: halt",
+            "    10: data 12345",
+        ];
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn truncated_instruction() {
+        let program = vec![1, 0, 0];
+        let lines = disassemble(&program);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|l| matches!(l, Line::Data { .. })));
+    }
+}
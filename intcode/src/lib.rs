@@ -1,4 +1,20 @@
+use std::any::Any;
 use std::collections::HashMap;
+use std::ops::Range;
+
+use utils::parse::{Input, ParseError};
+
+pub mod coverage;
+pub mod devices;
+pub mod disassembler;
+pub mod self_modification;
+pub mod state_cycle;
+pub mod symbolic;
+
+use coverage::Coverage;
+use devices::{Device, MappedDevice};
+use self_modification::SelfModificationTracker;
+use state_cycle::{CycleDetector, Snapshot, StateCycle};
 
 #[derive(Debug)]
 pub struct IntcodeComputer {
@@ -8,15 +24,19 @@ pub struct IntcodeComputer {
     pub data: HashMap<usize, i64>,
     pub input: Vec<i64>,
     pub output: Vec<i64>,
+    pub self_modifications: Option<SelfModificationTracker>,
+    pub cycle_detector: Option<CycleDetector>,
+    pub devices: Vec<MappedDevice>,
+    pub coverage: Option<Coverage>,
 }
 
 impl IntcodeComputer {
     pub fn parse_program(input: &str) -> Vec<i64> {
-        input
-            .trim()
-            .split(',')
-            .map(|s| s.parse::<i64>().unwrap())
-            .collect()
+        Self::try_parse_program(input).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_parse_program(text: &str) -> Result<Vec<i64>, ParseError> {
+        Input::new(text).comma_list(text.trim())
     }
 
     pub fn new(data: Vec<i64>, input: Option<Vec<i64>>) -> Self {
@@ -28,6 +48,10 @@ impl IntcodeComputer {
             // Allows us to pop from the vector more easily
             input: input.map_or_else(Vec::new, |input| input.into_iter().rev().collect()),
             output: Vec::new(),
+            self_modifications: None,
+            cycle_detector: None,
+            devices: Vec::new(),
+            coverage: None,
         }
     }
 
@@ -37,8 +61,93 @@ impl IntcodeComputer {
         computer
     }
 
+    // Starts recording writes to addresses that have already been executed. Only instructions
+    // executed from this point onwards count.
+    pub fn track_self_modification(&mut self) {
+        self.self_modifications = Some(SelfModificationTracker::default());
+    }
+
+    // Starts counting how many times each instruction is executed
+    pub fn collect_coverage(&mut self) {
+        self.coverage = Some(Coverage::default());
+    }
+
+    // Routes reads and writes to addresses in `range` through `device` instead of `data`. Ranges
+    // shouldn't overlap; if they do, whichever device was mapped first wins. Panics if the range
+    // is bigger than the device.
+    pub fn map_device(&mut self, range: Range<usize>, device: impl Device) {
+        assert!(
+            range.len() <= device.len(),
+            "can't map {} addresses onto a device with {}",
+            range.len(),
+            device.len()
+        );
+        self.devices.push(MappedDevice {
+            range,
+            device: Box::new(device),
+        });
+    }
+
+    // Finds the first mapped device of the given type
+    pub fn device<T: Device>(&self) -> Option<&T> {
+        self.devices.iter().find_map(|mapped| {
+            let device: &dyn Any = mapped.device.as_ref();
+            device.downcast_ref()
+        })
+    }
+
+    fn device_at(&mut self, address: usize) -> Option<(&mut Box<dyn Device>, usize)> {
+        self.devices
+            .iter_mut()
+            .find(|mapped| mapped.range.contains(&address))
+            .map(|mapped| (&mut mapped.device, address - mapped.range.start))
+    }
+
+    // Starts checking the state of the computer every time it reaches an input instruction. Once
+    // it comes back to a state it's been in before, `run` and `run_until_needs_input` stop at that
+    // input instruction without consuming anything, and `state_cycle` reports the cycle. Adding
+    // more input after that starts the detection over again.
+    pub fn detect_state_cycles(&mut self) {
+        self.cycle_detector = Some(CycleDetector::default());
+    }
+
+    pub fn state_cycle(&self) -> Option<StateCycle> {
+        self.cycle_detector.as_ref().and_then(|d| d.cycle)
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let mut memory: Vec<_> = self
+            .data
+            .iter()
+            .filter(|&(_, &v)| v != 0)
+            .map(|(&address, &value)| (address, value))
+            .collect();
+        memory.sort_unstable();
+        Snapshot {
+            pc: self.pc,
+            relative_base: self.relative_base,
+            input: self.input.clone(),
+            memory,
+            devices: self.devices.iter().map(|d| d.device.state()).collect(),
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.halted || self.state_cycle().is_some()
+    }
+
     fn step(&mut self) {
-        match self.data[&self.pc] % 100 {
+        let opcode = self.get_mem(self.pc) % 100;
+        for mapped in &mut self.devices {
+            mapped.device.tick();
+        }
+        if let Some(coverage) = &mut self.coverage {
+            coverage.record(self.pc);
+        }
+        if let Some(tracker) = &mut self.self_modifications {
+            tracker.record_execution(self.pc, instruction_length(opcode));
+        }
+        match opcode {
             1 => self.add(),
             2 => self.mult(),
             3 => self.input(),
@@ -54,6 +163,13 @@ impl IntcodeComputer {
     }
 
     fn get_mem(&self, address: usize) -> i64 {
+        let device = self
+            .devices
+            .iter()
+            .find(|mapped| mapped.range.contains(&address));
+        if let Some(mapped) = device {
+            return mapped.device.read(address - mapped.range.start);
+        }
         *self.data.get(&address).unwrap_or(&0)
     }
 
@@ -76,8 +192,16 @@ impl IntcodeComputer {
             2 => self.relative_base + parameter_value,
             _ => unreachable!(),
         };
-        self.data
-            .insert(address_to_write_to as usize, value_to_write);
+        let address_to_write_to = address_to_write_to as usize;
+        if let Some((device, offset)) = self.device_at(address_to_write_to) {
+            device.write(offset, value_to_write);
+            return;
+        }
+        if let Some(tracker) = &mut self.self_modifications {
+            let old_value = *self.data.get(&address_to_write_to).unwrap_or(&0);
+            tracker.record_write(self.pc, address_to_write_to, old_value, value_to_write);
+        }
+        self.data.insert(address_to_write_to, value_to_write);
     }
 
     fn binary_op<F>(&mut self, f: F)
@@ -99,6 +223,12 @@ impl IntcodeComputer {
     }
 
     fn input(&mut self) {
+        let snapshot = self.cycle_detector.is_some().then(|| self.snapshot());
+        if let (Some(detector), Some(snapshot)) = (&mut self.cycle_detector, snapshot) {
+            if detector.record(snapshot).is_some() {
+                return;
+            }
+        }
         let input_value = self.input.pop().expect("No more input!");
         self.write_to_param(1, input_value);
         self.pc += 2;
@@ -143,7 +273,7 @@ impl IntcodeComputer {
     }
 
     pub fn run(&mut self) {
-        while !self.halted {
+        while !self.is_stopped() {
             // println!("{:?}", self);
             self.step();
         }
@@ -151,19 +281,42 @@ impl IntcodeComputer {
 
     pub fn add_input(&mut self, new_input: i64) {
         self.input.insert(0, new_input);
+        // otherwise a computer stopped by a cycle could never get going again
+        if let Some(detector) = &mut self.cycle_detector {
+            if detector.cycle.is_some() {
+                *detector = CycleDetector::default();
+            }
+        }
     }
 
     pub fn run_until_needs_input(&mut self) {
-        while !(self.halted || (self.get_mem(self.pc) % 100 == 3 && self.input.is_empty())) {
+        while !(self.is_stopped() || (self.get_mem(self.pc) % 100 == 3 && self.input.is_empty())) {
             self.step();
         }
     }
 }
 
+// The number of memory cells taken up by an instruction, including its parameters
+pub const fn instruction_length(opcode: i64) -> usize {
+    match opcode % 100 {
+        1 | 2 | 7 | 8 => 4,
+        5 | 6 => 3,
+        3 | 4 | 9 => 2,
+        _ => 1,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parsing() {
+        assert_eq!(IntcodeComputer::parse_program("1,-2,99\n"), vec![1, -2, 99]);
+        let error = IntcodeComputer::try_parse_program("1,0,x,99\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+    }
+
     #[test]
     fn add() {
         let computer = IntcodeComputer::run_program(vec![1, 0, 4, 5, 99, 0], None);
@@ -275,4 +428,138 @@ mod tests {
         let computer = IntcodeComputer::run_program(program, None);
         assert_eq!(computer.output, vec![1125899906842624]);
     }
+
+    #[test]
+    fn self_modification() {
+        // Overwrites the parameter of the first instruction, then the opcode of the second
+        let program = vec![1101, 1, 1, 2, 1101, 2, 97, 4, 99];
+        let mut computer = IntcodeComputer::new(program, None);
+        computer.track_self_modification();
+        computer.run();
+        let tracker = computer.self_modifications.unwrap();
+        assert_eq!(
+            tracker.modifications,
+            vec![
+                self_modification::SelfModification {
+                    pc: 0,
+                    address: 2,
+                    old_value: 1,
+                    new_value: 2,
+                },
+                self_modification::SelfModification {
+                    pc: 4,
+                    address: 4,
+                    old_value: 1101,
+                    new_value: 99,
+                },
+            ]
+        );
+        assert!(!tracker.has_executed(9));
+    }
+
+    #[test]
+    fn no_self_modification() {
+        let program = vec![1101, 46, 1, 7, 104, 55, 99, 0];
+        let mut computer = IntcodeComputer::new(program, None);
+        computer.track_self_modification();
+        computer.run();
+        let tracker = computer.self_modifications.unwrap();
+        assert!(tracker.is_empty());
+        assert_eq!(tracker.to_string(), "no self-modifying writes\n");
+    }
+
+    #[test]
+    fn state_cycle() {
+        // Reads input forever, only remembering the last value
+        let program = vec![3, 5, 1105, 1, 0, 0];
+        let mut computer = IntcodeComputer::new(program, None);
+        computer.detect_state_cycles();
+        for _ in 0..3 {
+            computer.add_input(-1);
+            computer.run_until_needs_input();
+        }
+        assert_eq!(
+            computer.state_cycle(),
+            Some(StateCycle {
+                start: 1,
+                length: 1
+            })
+        );
+        assert_eq!(computer.input, vec![-1]);
+        // new input gets it going again
+        computer.add_input(5);
+        computer.run_until_needs_input();
+        assert_eq!(computer.state_cycle(), None);
+        assert_eq!(computer.get_mem(5), 5);
+    }
+
+    #[test]
+    fn devices_are_part_of_the_state() {
+        // Reads input forever, outputting the clock each time, which never repeats
+        let program = vec![3, 20, 4, 50, 1105, 1, 0];
+        let mut computer = IntcodeComputer::new(program, None);
+        computer.map_device(50..51, devices::Clock::default());
+        computer.detect_state_cycles();
+        for _ in 0..5 {
+            computer.add_input(-1);
+            computer.run_until_needs_input();
+        }
+        assert_eq!(computer.state_cycle(), None);
+        assert_eq!(computer.output.len(), 5);
+    }
+
+    #[test]
+    fn no_state_cycle() {
+        // Adds every input to a running total, so never repeats
+        let program = vec![3, 9, 1, 9, 10, 10, 1105, 1, 0, 0, 0];
+        let mut computer = IntcodeComputer::new(program, None);
+        computer.detect_state_cycles();
+        for _ in 0..10 {
+            computer.add_input(1);
+            computer.run_until_needs_input();
+        }
+        assert_eq!(computer.state_cycle(), None);
+        assert_eq!(computer.get_mem(10), 10);
+    }
+
+    #[test]
+    fn framebuffer_device() {
+        // Writes input to the pixel at (1, 0) and reads it back out
+        let program = vec![3, 102, 4, 102, 99];
+        let mut computer = IntcodeComputer::new(program, Some(vec![7]));
+        computer.map_device(100..104, devices::Framebuffer::new(2, 2));
+        computer.run();
+        assert_eq!(computer.output, vec![7]);
+        assert_eq!(computer.get_mem(102), 7);
+        assert!(!computer.data.contains_key(&102));
+        let framebuffer = computer.device::<devices::Framebuffer>().unwrap();
+        assert_eq!(framebuffer.pixel(1, 0), 7);
+        assert_eq!(framebuffer.to_string(), "..\n#.\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "can't map 5 addresses onto a device with 4")]
+    fn device_too_small() {
+        let mut computer = IntcodeComputer::new(vec![99], None);
+        computer.map_device(100..105, devices::Framebuffer::new(2, 2));
+    }
+
+    #[test]
+    fn clock_device() {
+        let program = vec![1101, 0, 0, 0, 4, 50, 4, 50, 99];
+        let mut computer = IntcodeComputer::new(program, None);
+        computer.map_device(50..51, devices::Clock::default());
+        computer.run();
+        assert_eq!(computer.output, vec![2, 3]);
+    }
+
+    #[test]
+    fn random_device() {
+        let program = vec![4, 10, 4, 10, 99];
+        let mut computer = IntcodeComputer::new(program, None);
+        computer.map_device(10..11, devices::Random::new(1));
+        computer.run();
+        assert_ne!(computer.output[0], computer.output[1]);
+        assert!(computer.output.iter().all(|&n| n >= 0));
+    }
 }
@@ -0,0 +1,61 @@
+// Tracks writes that land on addresses the computer has already executed as part of an
+// instruction. Anything that assumes the program text is fixed (disassembly, translation, decode
+// caching) can check this to know whether that assumption held.
+
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SelfModification {
+    pub pc: usize,
+    pub address: usize,
+    pub old_value: i64,
+    pub new_value: i64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SelfModificationTracker {
+    executed: HashSet<usize>,
+    pub modifications: Vec<SelfModification>,
+}
+
+impl SelfModificationTracker {
+    pub fn record_execution(&mut self, pc: usize, length: usize) {
+        self.executed.extend(pc..pc + length);
+    }
+
+    pub fn record_write(&mut self, pc: usize, address: usize, old_value: i64, new_value: i64) {
+        if self.executed.contains(&address) {
+            self.modifications.push(SelfModification {
+                pc,
+                address,
+                old_value,
+                new_value,
+            });
+        }
+    }
+
+    pub fn has_executed(&self, address: usize) -> bool {
+        self.executed.contains(&address)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modifications.is_empty()
+    }
+}
+
+impl fmt::Display for SelfModificationTracker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no self-modifying writes");
+        }
+        for m in &self.modifications {
+            writeln!(
+                f,
+                "pc {}: wrote {} to {} (was {})",
+                m.pc, m.new_value, m.address, m.old_value
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+// Detects when a computer reaches an input instruction in exactly the same state (memory, pc,
+// relative base, pending input and mapped devices) as it has been in before. Since the computer
+// is deterministic, it will then carry on repeating itself for as long as it is given the same
+// input.
+
+use std::collections::HashMap;
+
+// Both values are counted in input points, i.e. the number of times an input instruction has been
+// reached since detection started
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StateCycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+// Everything that decides what the computer does next. Output is left out, since it has no
+// effect on that.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Snapshot {
+    pub pc: usize,
+    pub relative_base: i64,
+    pub input: Vec<i64>,
+    // only the non-zero cells, in address order
+    pub memory: Vec<(usize, i64)>,
+    pub devices: Vec<Vec<i64>>,
+}
+
+// Whole snapshots are kept rather than hashes of them, so two different states can never be
+// mistaken for each other
+#[derive(Clone, Debug, Default)]
+pub struct CycleDetector {
+    seen: HashMap<Snapshot, usize>,
+    input_points: usize,
+    pub cycle: Option<StateCycle>,
+}
+
+impl CycleDetector {
+    // Records the state at an input point, returning the cycle if it's been seen before
+    pub fn record(&mut self, snapshot: Snapshot) -> Option<StateCycle> {
+        let now = self.input_points;
+        self.input_points += 1;
+        if let Some(&start) = self.seen.get(&snapshot) {
+            self.cycle = Some(StateCycle {
+                start,
+                length: now - start,
+            });
+        } else {
+            self.seen.insert(snapshot, now);
+        }
+        self.cycle
+    }
+}
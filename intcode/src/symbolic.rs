@@ -0,0 +1,441 @@
+// A symbolic version of the intcode computer. Chosen memory cells and inputs are replaced with
+// symbols, and the program is run with linear expressions over those symbols in place of numbers.
+// Once it halts, an expression can be solved for the symbol values that give a target result.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::ops::RangeInclusive;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(pub usize);
+
+// A linear expression: `constant + sum(coefficient * symbol)`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expr {
+    constant: i64,
+    terms: BTreeMap<Symbol, i64>,
+}
+
+impl Expr {
+    pub const fn constant(n: i64) -> Self {
+        Self {
+            constant: n,
+            terms: BTreeMap::new(),
+        }
+    }
+
+    pub fn symbol(symbol: Symbol) -> Self {
+        Self {
+            constant: 0,
+            terms: BTreeMap::from([(symbol, 1)]),
+        }
+    }
+
+    pub fn as_constant(&self) -> Option<i64> {
+        self.terms.is_empty().then_some(self.constant)
+    }
+
+    pub fn coefficient(&self, symbol: Symbol) -> i64 {
+        *self.terms.get(&symbol).unwrap_or(&0)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.constant += other.constant;
+        for (&symbol, &coefficient) in &other.terms {
+            *result.terms.entry(symbol).or_insert(0) += coefficient;
+        }
+        result.terms.retain(|_, &mut c| c != 0);
+        result
+    }
+
+    fn scale(&self, n: i64) -> Self {
+        Self {
+            constant: self.constant * n,
+            terms: self
+                .terms
+                .iter()
+                .filter(|_| n != 0)
+                .map(|(&symbol, &c)| (symbol, c * n))
+                .collect(),
+        }
+    }
+
+    // Only defined when at least one side is a constant, as otherwise the result isn't linear
+    pub fn mul(&self, other: &Self) -> Option<Self> {
+        match (self.as_constant(), other.as_constant()) {
+            (Some(n), _) => Some(other.scale(n)),
+            (_, Some(n)) => Some(self.scale(n)),
+            (None, None) => None,
+        }
+    }
+
+    pub fn evaluate(&self, values: &HashMap<Symbol, i64>) -> Option<i64> {
+        self.terms.iter().try_fold(self.constant, |total, (s, c)| {
+            values.get(s).map(|value| total + c * value)
+        })
+    }
+
+    /// Finds values for the symbols, each taken from its domain, such that the expression
+    /// evaluates to `target`. Domains are searched in the order given, so the solution found is
+    /// the same one a nested loop over those domains would find first. Returns `None` if there's
+    /// no solution, or if the expression mentions a symbol with no domain.
+    pub fn solve(
+        &self,
+        target: i64,
+        domains: &[(Symbol, RangeInclusive<i64>)],
+    ) -> Option<HashMap<Symbol, i64>> {
+        if self
+            .terms
+            .keys()
+            .any(|s| domains.iter().all(|(d, _)| d != s))
+        {
+            return None;
+        }
+        let mut assignment = HashMap::new();
+        self.solve_from(target - self.constant, domains, &mut assignment)
+            .then_some(assignment)
+    }
+
+    fn solve_from(
+        &self,
+        remainder: i64,
+        domains: &[(Symbol, RangeInclusive<i64>)],
+        assignment: &mut HashMap<Symbol, i64>,
+    ) -> bool {
+        match domains {
+            [] => remainder == 0,
+            [(symbol, range)] => {
+                let coefficient = self.coefficient(*symbol);
+                let value = if coefficient == 0 {
+                    (remainder == 0).then_some(*range.start())
+                } else {
+                    (remainder % coefficient == 0).then_some(remainder / coefficient)
+                };
+                match value.filter(|v| range.contains(v)) {
+                    Some(value) => {
+                        assignment.insert(*symbol, value);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            [(symbol, range), rest @ ..] => {
+                let coefficient = self.coefficient(*symbol);
+                for value in range.clone() {
+                    assignment.insert(*symbol, value);
+                    if self.solve_from(remainder - coefficient * value, rest, assignment) {
+                        return true;
+                    }
+                    if coefficient == 0 {
+                        // every other value would lead to the same outcome
+                        break;
+                    }
+                }
+                assignment.remove(symbol);
+                false
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.constant)?;
+        for (Symbol(s), c) in &self.terms {
+            write!(f, " + {}*s{}", c, s)?;
+        }
+        Ok(())
+    }
+}
+
+// Each variant records the pc of the instruction that caused it
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SymbolicError {
+    NonLinear(usize),
+    SymbolicAddress(usize),
+    SymbolicComparison(usize),
+    SymbolicBranch(usize),
+    SymbolicInstruction(usize),
+    NoInput(usize),
+    // the whole instruction, so a bad parameter mode counts too
+    InvalidOpcode { pc: usize, opcode: i64 },
+}
+
+// Memory cells that couldn't be represented hold the reason why, and only cause an error once
+// something actually depends on them. This lets programs overwrite such cells harmlessly.
+type Value = Result<Expr, SymbolicError>;
+
+#[derive(Debug)]
+pub struct SymbolicComputer {
+    pub pc: usize,
+    pub relative_base: i64,
+    pub halted: bool,
+    pub data: HashMap<usize, Value>,
+    pub input: Vec<Expr>,
+    pub output: Vec<Expr>,
+    next_symbol: usize,
+}
+
+impl SymbolicComputer {
+    pub fn new(data: Vec<i64>, input: Option<Vec<i64>>) -> Self {
+        Self {
+            pc: 0,
+            relative_base: 0,
+            halted: false,
+            data: data
+                .into_iter()
+                .map(|n| Ok(Expr::constant(n)))
+                .enumerate()
+                .collect(),
+            input: input.map_or_else(Vec::new, |input| {
+                input.into_iter().rev().map(Expr::constant).collect()
+            }),
+            output: Vec::new(),
+            next_symbol: 0,
+        }
+    }
+
+    fn new_symbol(&mut self) -> Symbol {
+        self.next_symbol += 1;
+        Symbol(self.next_symbol - 1)
+    }
+
+    // Replaces the value at `address` with a fresh symbol
+    pub fn make_symbolic(&mut self, address: usize) -> Symbol {
+        let symbol = self.new_symbol();
+        self.data.insert(address, Ok(Expr::symbol(symbol)));
+        symbol
+    }
+
+    pub fn add_input(&mut self, new_input: i64) {
+        self.input.insert(0, Expr::constant(new_input));
+    }
+
+    pub fn add_symbolic_input(&mut self) -> Symbol {
+        let symbol = self.new_symbol();
+        self.input.insert(0, Expr::symbol(symbol));
+        symbol
+    }
+
+    pub fn get_mem(&self, address: usize) -> Value {
+        self.data
+            .get(&address)
+            .cloned()
+            .unwrap_or(Ok(Expr::constant(0)))
+    }
+
+    fn concrete(
+        &self,
+        value: Value,
+        error: fn(usize) -> SymbolicError,
+    ) -> Result<i64, SymbolicError> {
+        value?.as_constant().ok_or(error(self.pc))
+    }
+
+    fn opcode(&self) -> Result<i64, SymbolicError> {
+        self.concrete(self.get_mem(self.pc), SymbolicError::SymbolicInstruction)
+    }
+
+    fn read_from_param(&self, position: u32) -> Result<Value, SymbolicError> {
+        let opcode = self.opcode()?;
+        let parameter_value = self.get_mem(self.pc + position as usize);
+        let address = |offset: i64| {
+            let address = self.concrete(parameter_value.clone(), SymbolicError::SymbolicAddress)?;
+            Ok::<_, SymbolicError>((offset + address) as usize)
+        };
+        Ok(match (opcode / (10i64.pow(position + 1))) % 10 {
+            0 => address(0).and_then(|a| self.get_mem(a)),
+            1 => parameter_value,
+            2 => address(self.relative_base).and_then(|a| self.get_mem(a)),
+            _ => return Err(self.invalid_opcode(opcode)),
+        })
+    }
+
+    fn write_to_param(
+        &mut self,
+        position: u32,
+        value_to_write: Value,
+    ) -> Result<(), SymbolicError> {
+        let opcode = self.opcode()?;
+        let parameter_value = self.get_mem(self.pc + position as usize);
+        let parameter_value = self.concrete(parameter_value, SymbolicError::SymbolicAddress)?;
+        let address_to_write_to = match (opcode / (10i64.pow(position + 1))) % 10 {
+            0 => parameter_value,
+            2 => self.relative_base + parameter_value,
+            _ => return Err(self.invalid_opcode(opcode)),
+        };
+        self.data
+            .insert(address_to_write_to as usize, value_to_write);
+        Ok(())
+    }
+
+    fn binary_op<F>(&mut self, f: F) -> Result<(), SymbolicError>
+    where
+        F: Fn(&Expr, &Expr) -> Option<Expr>,
+    {
+        let parameter1 = self.read_from_param(1)?;
+        let parameter2 = self.read_from_param(2)?;
+        let error = match self.opcode()? % 100 {
+            2 => SymbolicError::NonLinear(self.pc),
+            _ => SymbolicError::SymbolicComparison(self.pc),
+        };
+        let result = parameter1.and_then(|x| parameter2.and_then(|y| f(&x, &y).ok_or(error)));
+        self.write_to_param(3, result)?;
+        self.pc += 4;
+        Ok(())
+    }
+
+    fn step(&mut self) -> Result<(), SymbolicError> {
+        match self.opcode()? % 100 {
+            1 => self.binary_op(|x, y| Some(x.add(y))),
+            2 => self.binary_op(Expr::mul),
+            3 => {
+                let input_value = self.input.pop().ok_or(SymbolicError::NoInput(self.pc))?;
+                self.write_to_param(1, Ok(input_value))?;
+                self.pc += 2;
+                Ok(())
+            }
+            4 => {
+                let value = self.read_from_param(1)??;
+                self.output.push(value);
+                self.pc += 2;
+                Ok(())
+            }
+            5 => self.jump_if(|n| n != 0),
+            6 => self.jump_if(|n| n == 0),
+            7 => self.binary_op(|x, y| compare(x, y, |d| d < 0)),
+            8 => self.binary_op(|x, y| compare(x, y, |d| d == 0)),
+            9 => {
+                let value = self.read_from_param(1)?;
+                self.relative_base += self.concrete(value, SymbolicError::SymbolicAddress)?;
+                self.pc += 2;
+                Ok(())
+            }
+            99 => {
+                self.halted = true;
+                Ok(())
+            }
+            opcode => Err(self.invalid_opcode(opcode)),
+        }
+    }
+
+    fn invalid_opcode(&self, opcode: i64) -> SymbolicError {
+        SymbolicError::InvalidOpcode {
+            pc: self.pc,
+            opcode,
+        }
+    }
+
+    fn jump_if(&mut self, condition: fn(i64) -> bool) -> Result<(), SymbolicError> {
+        let value = self.read_from_param(1)?;
+        if condition(self.concrete(value, SymbolicError::SymbolicBranch)?) {
+            let target = self.read_from_param(2)?;
+            self.pc = self.concrete(target, SymbolicError::SymbolicBranch)? as usize;
+        } else {
+            self.pc += 3;
+        }
+        Ok(())
+    }
+
+    pub fn run(&mut self) -> Result<(), SymbolicError> {
+        while !self.halted {
+            self.step()?;
+        }
+        Ok(())
+    }
+}
+
+// Comparisons can still be decided when both sides differ by a constant, e.g. `x + 1` and `x`
+fn compare(x: &Expr, y: &Expr, f: fn(i64) -> bool) -> Option<Expr> {
+    let difference = x.add(&y.scale(-1)).as_constant()?;
+    Some(Expr::constant(i64::from(f(difference))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concrete_program() {
+        let mut computer = SymbolicComputer::new(vec![1101, 46, 1, 7, 104, 55, 99, 0], None);
+        computer.run().unwrap();
+        assert_eq!(computer.get_mem(7), Ok(Expr::constant(47)));
+        assert_eq!(computer.output, vec![Expr::constant(55)]);
+    }
+
+    #[test]
+    fn linear_expressions() {
+        // output (a * 3) + b
+        let program = vec![1002, 11, 3, 13, 1, 13, 12, 13, 4, 13, 99, 0, 0, 0];
+        let mut computer = SymbolicComputer::new(program, None);
+        let a = computer.make_symbolic(11);
+        let b = computer.make_symbolic(12);
+        computer.run().unwrap();
+        let result = &computer.output[0];
+        assert_eq!(result.coefficient(a), 3);
+        assert_eq!(result.coefficient(b), 1);
+        let solution = result.solve(20, &[(a, 0..=10), (b, 0..=10)]).unwrap();
+        assert_eq!(solution, HashMap::from([(a, 4), (b, 8)]));
+    }
+
+    #[test]
+    fn symbolic_input() {
+        let program = vec![3, 9, 1001, 9, 5, 9, 4, 9, 99, 0];
+        let mut computer = SymbolicComputer::new(program, None);
+        let x = computer.add_symbolic_input();
+        computer.run().unwrap();
+        let solution = computer.output[0].solve(12, &[(x, -100..=100)]);
+        assert_eq!(solution, Some(HashMap::from([(x, 7)])));
+    }
+
+    #[test]
+    fn overwritten_unknowns_are_harmless() {
+        // Reads from a symbolic address into mem[3], then overwrites it with a known value
+        let program = vec![1, 0, 0, 3, 1101, 2, 2, 3, 99];
+        let mut computer = SymbolicComputer::new(program, None);
+        computer.make_symbolic(1);
+        computer.run().unwrap();
+        assert_eq!(computer.get_mem(3), Ok(Expr::constant(4)));
+    }
+
+    #[test]
+    fn errors() {
+        let mut computer = SymbolicComputer::new(vec![2, 5, 6, 0, 99, 0, 0], None);
+        computer.make_symbolic(5);
+        computer.make_symbolic(6);
+        computer.run().unwrap();
+        assert_eq!(computer.get_mem(0), Err(SymbolicError::NonLinear(0)));
+        let mut computer = SymbolicComputer::new(vec![1005, 4, 99, 99, 0], None);
+        computer.make_symbolic(4);
+        assert_eq!(computer.run(), Err(SymbolicError::SymbolicBranch(0)));
+        let mut computer = SymbolicComputer::new(vec![3, 0, 99], None);
+        assert_eq!(computer.run(), Err(SymbolicError::NoInput(0)));
+        let mut computer = SymbolicComputer::new(vec![1101, 1, 1, 5, 42, 0], None);
+        let invalid = SymbolicError::InvalidOpcode { pc: 4, opcode: 42 };
+        assert_eq!(computer.run(), Err(invalid));
+        // a parameter mode that doesn't exist
+        let mut computer = SymbolicComputer::new(vec![301, 0, 0, 0, 99], None);
+        let invalid = SymbolicError::InvalidOpcode { pc: 0, opcode: 301 };
+        assert_eq!(computer.run(), Err(invalid));
+    }
+
+    #[test]
+    fn comparisons() {
+        // mem[9] = (x + 1) < x
+        let program = vec![1001, 10, 1, 11, 7, 11, 10, 9, 99, 0, 0, 0];
+        let mut computer = SymbolicComputer::new(program, None);
+        computer.make_symbolic(10);
+        computer.run().unwrap();
+        assert_eq!(computer.get_mem(9), Ok(Expr::constant(0)));
+    }
+
+    #[test]
+    fn solve_without_solution() {
+        let x = Symbol(0);
+        let expr = Expr::symbol(x).scale(2);
+        assert_eq!(expr.solve(3, &[(x, 0..=10)]), None);
+        assert_eq!(expr.solve(30, &[(x, 0..=10)]), None);
+        assert_eq!(expr.solve(4, &[]), None);
+    }
+}
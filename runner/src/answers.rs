@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+
+use utils::parse::{Input, ParseError};
+use utils::solution::Part;
+
+// The expected answers for one set of inputs. The file has one answer per line, as the day, the
+// part and the answer separated by spaces, like `7 b 4248984`. Blank lines and lines starting
+// with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, Part), String>);
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let input = Input::new(text);
+        let mut answers = BTreeMap::new();
+        for line in input.lines().filter(|line| !line.starts_with('#')) {
+            let (day, rest) = input.split_once(line, " ")?;
+            let (part, answer) = input.split_once(rest.trim_start(), " ")?;
+            let day = match input.number(day)? {
+                day @ 1..=25 => day,
+                _ => return Err(input.error(day, "expected a day from 1 to 25")),
+            };
+            let part = match part {
+                "a" => Part::A,
+                "b" => Part::B,
+                _ => return Err(input.error(part, "expected `a` or `b`")),
+            };
+            if answers
+                .insert((day, part), answer.trim().to_string())
+                .is_some()
+            {
+                return Err(input.error(line, "this answer is already in the file"));
+            }
+        }
+        Ok(Self(answers))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse("# comment\n1 a 3216868\n\n8  b LBRCE\n").unwrap();
+        assert_eq!(answers.get(1, Part::A), Some("3216868"));
+        assert_eq!(answers.get(8, Part::B), Some("LBRCE"));
+        assert_eq!(answers.get(1, Part::B), None);
+        let error = Answers::parse("1 a 1\n1 c 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        let error = Answers::parse("26 a 1\n").unwrap_err();
+        assert_eq!(error.message, "expected a day from 1 to 25");
+        let error = Answers::parse("1 a 1\n1 a 2\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(Answers::parse("1 a\n").is_err());
+    }
+
+    // Keeps the checked in answers in step with the registry
+    #[test]
+    fn every_part_has_an_answer() {
+        let answers = Answers::parse(include_str!("../../answers.txt")).unwrap();
+        for entry in solutions::all() {
+            for &part in entry.parts {
+                assert!(
+                    answers.get(entry.day, part).is_some(),
+                    "{} {}",
+                    entry.day,
+                    part
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::path::PathBuf;
+
+use utils::solution::Part;
+
+pub const USAGE: &str = "usage: runner <day|all> [a|b] [--input <path>|-]
+       runner verify [--answers <path>] [--inputs <dir>]
+
+  day        a number from 1 to 25, or `all` for every day
+  a|b        just the one part, rather than both
+  --input    read the input from <path>, or from stdin if it's `-`,
+             instead of the day's own input.txt
+  verify     run every day and check the answers against <path>,
+             which is answers.txt at the top of the workspace by default
+  --inputs   read each day's input from <dir>/dNN.txt, like d07.txt";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(Args),
+    Verify(Verify),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Args {
+    pub days: Days,
+    // None for every part the day has
+    pub part: Option<Part>,
+    pub input: InputSource,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verify {
+    // None for the checked in answers
+    pub answers: Option<PathBuf>,
+    pub input: InputSource,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Days {
+    All,
+    One(u8),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    // the input.txt checked in next to the day's code
+    Default,
+    File(PathBuf),
+    Stdin,
+    // a file for each day, named after it
+    Directory(PathBuf),
+}
+
+// Everything after the program name
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let days = match args.next().as_deref() {
+        None => return Err("expected a day".to_string()),
+        Some("verify") => return parse_verify(args).map(Command::Verify),
+        Some("all") => Days::All,
+        Some(day) => match day.parse() {
+            Ok(day @ 1..=25) => Days::One(day),
+            _ => return Err(format!("`{}` isn't a day from 1 to 25", day)),
+        },
+    };
+    let mut part = None;
+    let mut input = InputSource::Default;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "a" => part = Some(Part::A),
+            "b" => part = Some(Part::B),
+            "--input" | "-i" => {
+                input = match path_after(&arg, &mut args)? {
+                    path if path.as_os_str() == "-" => InputSource::Stdin,
+                    path => InputSource::File(path),
+                }
+            }
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+    if days == Days::All && input != InputSource::Default {
+        return Err("every day needs its own input, so `all` can't take --input".to_string());
+    }
+    Ok(Command::Run(Args { days, part, input }))
+}
+
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<Verify, String> {
+    let mut verify = Verify {
+        answers: None,
+        input: InputSource::Default,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => verify.answers = Some(path_after(&arg, &mut args)?),
+            "--inputs" => verify.input = InputSource::Directory(path_after(&arg, &mut args)?),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+    Ok(verify)
+}
+
+fn path_after(flag: &str, args: &mut impl Iterator<Item = String>) -> Result<PathBuf, String> {
+    args.next()
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("expected a path after `{}`", flag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Command, String> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    fn run_args(args: &str) -> Args {
+        match parse_str(args) {
+            Ok(Command::Run(args)) => args,
+            other => panic!("expected run arguments, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            run_args("all"),
+            Args {
+                days: Days::All,
+                part: None,
+                input: InputSource::Default
+            }
+        );
+        assert_eq!(
+            run_args("7 b --input other.txt"),
+            Args {
+                days: Days::One(7),
+                part: Some(Part::B),
+                input: InputSource::File("other.txt".into())
+            }
+        );
+        assert_eq!(run_args("25 -i -").input, InputSource::Stdin);
+    }
+
+    #[test]
+    fn verify() {
+        assert_eq!(
+            parse_str("verify"),
+            Ok(Command::Verify(Verify {
+                answers: None,
+                input: InputSource::Default
+            }))
+        );
+        assert_eq!(
+            parse_str("verify --inputs theirs --answers theirs/answers.txt"),
+            Ok(Command::Verify(Verify {
+                answers: Some("theirs/answers.txt".into()),
+                input: InputSource::Directory("theirs".into())
+            }))
+        );
+        assert!(parse_str("verify --inputs").is_err());
+        assert!(parse_str("verify 5").is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_str("").is_err());
+        assert!(parse_str("0").is_err());
+        assert!(parse_str("26").is_err());
+        assert!(parse_str("five").is_err());
+        assert!(parse_str("5 c").is_err());
+        assert!(parse_str("5 --input").is_err());
+        assert!(parse_str("all --input x.txt").is_err());
+    }
+}
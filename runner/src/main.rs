@@ -0,0 +1,161 @@
+use std::io::{self, Read};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::slice;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use std::{env, fs};
+
+use args::{Args, Command, Days, InputSource};
+use utils::solution::{Entry, Part};
+
+mod answers;
+mod args;
+mod verify;
+
+fn main() -> ExitCode {
+    let succeeded = match args::parse(env::args().skip(1)) {
+        Ok(Command::Run(args)) => run(&args),
+        Ok(Command::Verify(args)) => verify::verify(&args),
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, args::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+// Prints each answer as it's found, returning whether they all were
+fn run(args: &Args) -> bool {
+    let entries = match args.days {
+        Days::All => solutions::all().to_vec(),
+        Days::One(day) => solutions::get(day).into_iter().collect(),
+    };
+    let mut succeeded = true;
+    for entry in entries {
+        let input = match read_input(entry.day, &args.input) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("day {}: couldn't read the input: {}", entry.day, e);
+                succeeded = false;
+                continue;
+            }
+        };
+        let parts = match &args.part {
+            Some(part) => slice::from_ref(part),
+            None => entry.parts,
+        };
+        for &part in parts {
+            let start = Instant::now();
+            let answer = run_part(&entry, part, &input);
+            let elapsed = start.elapsed();
+            match answer {
+                Ok(answer) => println!(
+                    "day {} part {}: {} ({:.2?})",
+                    entry.day, part, answer, elapsed
+                ),
+                Err(e) => {
+                    eprintln!("day {} part {}: {} ({:.2?})", entry.day, part, e, elapsed);
+                    succeeded = false;
+                }
+            }
+        }
+    }
+    succeeded
+}
+
+// Runs one part, turning a panic into an error too, so that one day choking on an input doesn't
+// stop the rest from running
+fn run_part(entry: &Entry, part: Part, input: &str) -> Result<String, String> {
+    // The panic is reported along with everything else, so while the part runs, the hook only
+    // notes where it happened rather than printing anything
+    let location = Arc::new(Mutex::new(None));
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new({
+        let location = Arc::clone(&location);
+        move |info| *location.lock().unwrap() = info.location().map(|l| l.to_string())
+    }));
+    let result = panic::catch_unwind(|| entry.run(part, input));
+    panic::set_hook(previous_hook);
+    match result {
+        Ok(answer) => answer.map_err(|e| e.to_string()),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            match location.lock().unwrap().take() {
+                Some(location) => Err(format!("panicked at {}: {}", location, message)),
+                None => Err(format!("panicked: {}", message)),
+            }
+        }
+    }
+}
+
+fn read_input(day: u8, source: &InputSource) -> io::Result<String> {
+    match source {
+        InputSource::Default => fs::read_to_string(default_input(day)),
+        InputSource::File(path) => fs::read_to_string(path),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        InputSource::Directory(dir) => fs::read_to_string(dir.join(format!("d{:02}.txt", day))),
+    }
+}
+
+// A path relative to the top of the workspace
+fn workspace_path(path: impl AsRef<Path>) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(path)
+}
+
+// Where the day's own input is checked in
+fn default_input(day: u8) -> PathBuf {
+    workspace_path(format!("d{:02}/src/input.txt", day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Display;
+    use utils::solution::{Solution, SolutionError};
+
+    // part b chokes on anything that isn't a number
+    struct Choosy(String);
+
+    impl Solution for Choosy {
+        const DAY: u8 = 0;
+
+        fn parse(input: &str) -> Result<Self, SolutionError> {
+            Ok(Self(input.to_string()))
+        }
+
+        fn part_a(&self) -> Result<impl Display, SolutionError> {
+            Ok(self.0.len())
+        }
+
+        fn part_b(&self) -> Result<impl Display, SolutionError> {
+            Ok(self.0.parse::<i64>().expect("not a number"))
+        }
+    }
+
+    #[test]
+    fn panics_are_errors() {
+        let entry = Entry::of::<Choosy>();
+        assert_eq!(run_part(&entry, Part::B, "12"), Ok("12".to_string()));
+        let error = run_part(&entry, Part::B, "x").unwrap_err();
+        assert!(
+            error.starts_with("panicked at runner/src/main.rs:"),
+            "{}",
+            error
+        );
+        assert!(error.contains(": not a number"), "{}", error);
+    }
+}
@@ -0,0 +1,107 @@
+use std::fs;
+use std::time::{Duration, Instant};
+
+use utils::solution::Part;
+
+use crate::answers::Answers;
+use crate::args::Verify;
+
+enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    Error(String),
+    // there's nothing to compare the answer with
+    Unchecked(String),
+    // there's no solution to run, like on the last day, which is played by hand
+    Unsolved,
+}
+
+// Runs every part of every day and prints a table of how each one did, returning whether they
+// all got the expected answer. Anything missing from the answers file, or without a solution to
+// run, is reported as unchecked but doesn't count as a failure, so a partial file still works.
+pub fn verify(args: &Verify) -> bool {
+    let path = args
+        .answers
+        .clone()
+        .unwrap_or_else(|| crate::workspace_path("answers.txt"));
+    let answers = match fs::read_to_string(&path) {
+        Ok(text) => match Answers::parse(&text) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                return false;
+            }
+        },
+        Err(e) => {
+            eprintln!("couldn't read {}: {}", path.display(), e);
+            return false;
+        }
+    };
+
+    println!(
+        "{:>3}  {:<4}  {:<9}  {:>10}",
+        "day", "part", "result", "time"
+    );
+    let mut counts = [0; 4];
+    let mut total = Duration::ZERO;
+    for entry in solutions::all() {
+        let input = crate::read_input(entry.day, &args.input);
+        for part in [Part::A, Part::B] {
+            let start = Instant::now();
+            let outcome = match &input {
+                _ if !entry.parts.contains(&part) => Outcome::Unsolved,
+                Err(e) => Outcome::Error(format!("couldn't read the input: {}", e)),
+                Ok(input) => check(
+                    crate::run_part(&entry, part, input),
+                    answers.get(entry.day, part),
+                ),
+            };
+            let elapsed = start.elapsed();
+            total += elapsed;
+            print_row(entry.day, part, &outcome, elapsed);
+            counts[match outcome {
+                Outcome::Pass => 0,
+                Outcome::Fail { .. } => 1,
+                Outcome::Error(_) => 2,
+                Outcome::Unchecked(_) | Outcome::Unsolved => 3,
+            }] += 1;
+        }
+    }
+    let [passed, failed, errors, unchecked] = counts;
+    println!(
+        "\n{} passed, {} failed, {} errors, {} unchecked in {:.2?}",
+        passed, failed, errors, unchecked, total
+    );
+    failed == 0 && errors == 0
+}
+
+fn check(answer: Result<String, String>, expected: Option<&str>) -> Outcome {
+    match (answer, expected) {
+        (Err(e), _) => Outcome::Error(e),
+        (Ok(actual), None) => Outcome::Unchecked(actual),
+        (Ok(actual), Some(expected)) if actual == expected => Outcome::Pass,
+        (Ok(actual), Some(expected)) => Outcome::Fail {
+            expected: expected.to_string(),
+            actual,
+        },
+    }
+}
+
+fn print_row(day: u8, part: Part, outcome: &Outcome, elapsed: Duration) {
+    let (result, detail) = match outcome {
+        Outcome::Pass => ("pass", String::new()),
+        Outcome::Fail { expected, actual } => {
+            ("FAIL", format!("expected {}, got {}", expected, actual))
+        }
+        // only the first line, since parse errors also show where the problem is
+        Outcome::Error(e) => ("ERROR", e.lines().next().unwrap_or("").to_string()),
+        Outcome::Unchecked(actual) => ("unchecked", format!("got {}", actual)),
+        Outcome::Unsolved => ("unchecked", "no solution to run".to_string()),
+    };
+    let time = format!("{:.2?}", elapsed);
+    let row = format!(
+        "{:>3}  {:<4}  {:<9}  {:>10}  {}",
+        day, part, result, time, detail
+    );
+    println!("{}", row.trim_end());
+}
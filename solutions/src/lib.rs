@@ -0,0 +1,61 @@
+use utils::solution::Entry;
+
+// Every day's solution, so tools can run any of them by number
+pub fn all() -> [Entry; 25] {
+    [
+        Entry::of::<d01::Day01>(),
+        Entry::of::<d02::Day02>(),
+        Entry::of::<d03::Day03>(),
+        Entry::of::<d04::Day04>(),
+        Entry::of::<d05::Day05>(),
+        Entry::of::<d06::Day06>(),
+        Entry::of::<d07::Day07>(),
+        Entry::of::<d08::Day08>(),
+        Entry::of::<d09::Day09>(),
+        Entry::of::<d10::Day10>(),
+        Entry::of::<d11::Day11>(),
+        Entry::of::<d12::Day12>(),
+        Entry::of::<d13::Day13>(),
+        Entry::of::<d14::Day14>(),
+        Entry::of::<d15::Day15>(),
+        Entry::of::<d16::Day16>(),
+        Entry::of::<d17::Day17>(),
+        Entry::of::<d18::Day18>(),
+        Entry::of::<d19::Day19>(),
+        Entry::of::<d20::Day20>(),
+        Entry::of::<d21::Day21>(),
+        Entry::of::<d22::Day22>(),
+        Entry::of::<d23::Day23>(),
+        Entry::of::<d24::Day24>(),
+        Entry::of::<d25::Day25>(),
+    ]
+}
+
+pub fn get(day: u8) -> Option<Entry> {
+    all().into_iter().find(|entry| entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::solution::{Part, SolutionError};
+
+    #[test]
+    fn registry() {
+        let days: Vec<_> = all().iter().map(|entry| entry.day).collect();
+        assert_eq!(days, (1..=25).collect::<Vec<_>>());
+        assert!(get(0).is_none());
+        assert!(get(26).is_none());
+        let input = include_str!("../../d01/src/input.txt");
+        assert_eq!(
+            get(1).unwrap().run(Part::A, input),
+            Ok("3216868".to_string())
+        );
+        assert_eq!(get(25).unwrap().parts, []);
+        let error = get(25).unwrap().run(Part::A, "").unwrap_err();
+        assert!(matches!(error, SolutionError::NoAnswer(_)));
+        // a broken intcode program is turned away before it gets anywhere near a computer
+        let error = get(9).unwrap().run(Part::A, "1,x,99\n").unwrap_err();
+        assert!(matches!(error, SolutionError::Parse(_)));
+    }
+}
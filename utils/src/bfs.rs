@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     hash::Hash,
 };
 
@@ -39,6 +39,89 @@ where
     None
 }
 
+// distances {{{1
+// The number of steps from `start` to every node reachable from it
+pub fn bfs_distances<Node, NeighbourFn>(
+    neighbours: NeighbourFn,
+    start: Node,
+) -> HashMap<Node, usize>
+where
+    NeighbourFn: Fn(&Node) -> Vec<Node>,
+    Node: Hash + Eq + Copy,
+{
+    multi_source_distances(neighbours, [start])
+}
+
+// The number of steps to every reachable node from whichever of `starts` is closest to it
+pub fn multi_source_distances<Node, NeighbourFn, Starts>(
+    neighbours: NeighbourFn,
+    starts: Starts,
+) -> HashMap<Node, usize>
+where
+    NeighbourFn: Fn(&Node) -> Vec<Node>,
+    Node: Hash + Eq + Copy,
+    Starts: IntoIterator<Item = Node>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if distances.insert(start, 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+    while let Some(pos) = queue.pop_front() {
+        let distance = distances[&pos];
+        for neighbour in neighbours(&pos) {
+            if let Entry::Vacant(e) = distances.entry(neighbour) {
+                e.insert(distance + 1);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    distances
+}
+
+// How many steps it takes to fill everything reachable when spreading out from all of `starts`
+// at once, or `None` if there are no starts
+pub fn flood_fill_time<Node, NeighbourFn, Starts>(
+    neighbours: NeighbourFn,
+    starts: Starts,
+) -> Option<usize>
+where
+    NeighbourFn: Fn(&Node) -> Vec<Node>,
+    Node: Hash + Eq + Copy,
+    Starts: IntoIterator<Item = Node>,
+{
+    multi_source_distances(neighbours, starts)
+        .into_values()
+        .max()
+}
+
+// components {{{1
+// Groups `nodes` into sets that can all reach each other. `neighbours` should be symmetric, and
+// may lead to nodes outside of `nodes`, which are then included in the components too.
+pub fn connected_components<Node, NeighbourFn, Nodes>(
+    neighbours: NeighbourFn,
+    nodes: Nodes,
+) -> Vec<HashSet<Node>>
+where
+    NeighbourFn: Fn(&Node) -> Vec<Node>,
+    Node: Hash + Eq + Copy,
+    Nodes: IntoIterator<Item = Node>,
+{
+    let mut seen: HashSet<Node> = HashSet::new();
+    let mut result = Vec::new();
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component: HashSet<_> = bfs_distances(&neighbours, node).into_keys().collect();
+        seen.extend(&component);
+        result.push(component);
+    }
+    result
+}
+
 // tests {{{1
 mod tests {
 
@@ -136,4 +219,54 @@ mod tests {
         let result = bfs(neighbours, done, start_pos);
         assert_eq!(result, None);
     }
+
+    // distances {{{2
+    #[test]
+    fn distances_test() {
+        use super::*;
+        use crate::v2::V2;
+        // An open 3x3 grid
+        let neighbours = |&pos: &V2| {
+            pos.taxicab_neighbours()
+                .into_iter()
+                .filter(|&V2(y, x)| (0..3).contains(&y) && (0..3).contains(&x))
+                .collect()
+        };
+        let distances = bfs_distances(neighbours, V2(0, 0));
+        assert_eq!(distances.len(), 9);
+        assert_eq!(distances[&V2(0, 0)], 0);
+        assert_eq!(distances[&V2(1, 1)], 2);
+        assert_eq!(distances[&V2(2, 2)], 4);
+        let distances = multi_source_distances(neighbours, [V2(0, 0), V2(2, 2)]);
+        assert_eq!(distances[&V2(2, 2)], 0);
+        assert_eq!(distances[&V2(0, 2)], 2);
+        assert_eq!(distances[&V2(1, 2)], 1);
+        assert_eq!(flood_fill_time(neighbours, [V2(1, 1)]), Some(2));
+        assert_eq!(flood_fill_time(neighbours, [V2(0, 0), V2(2, 2)]), Some(2));
+        assert_eq!(flood_fill_time(neighbours, []), None);
+    }
+
+    // components {{{2
+    #[test]
+    fn connected_components_test() {
+        use super::*;
+        // 1 - 2 - 3   4 - 5   6
+        let neighbours = |&n: &i32| match n {
+            1 => vec![2],
+            2 => vec![1, 3],
+            3 => vec![2],
+            4 => vec![5],
+            5 => vec![4],
+            _ => vec![],
+        };
+        let components = connected_components(neighbours, 1..=6);
+        assert_eq!(
+            components,
+            vec![
+                HashSet::from([1, 2, 3]),
+                HashSet::from([4, 5]),
+                HashSet::from([6])
+            ]
+        );
+    }
 }
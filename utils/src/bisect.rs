@@ -0,0 +1,118 @@
+// Binary searches over the integers, for monotone predicates: ones that are false up to some
+// point and true from then on, or the other way round for the `last_true` functions.
+
+// bounded {{{1
+// The smallest n in low..high where `pred(n)` is true, if there is one
+pub fn first_true<PredFn>(low: i64, high: i64, pred: PredFn) -> Option<i64>
+where
+    PredFn: Fn(i64) -> bool,
+{
+    let (mut low, mut high_bound) = (low, high);
+    while low < high_bound {
+        let mid = low + (high_bound - low) / 2;
+        if pred(mid) {
+            high_bound = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    (low < high).then_some(low)
+}
+
+// The largest n in low..high where `pred(n)` is true, for a predicate that's true up to some
+// point and false from then on
+pub fn last_true<PredFn>(low: i64, high: i64, pred: PredFn) -> Option<i64>
+where
+    PredFn: Fn(i64) -> bool,
+{
+    let first_false = first_true(low, high, |n| !pred(n)).unwrap_or(high);
+    (first_false > low).then_some(first_false - 1)
+}
+
+// unbounded {{{1
+// Like `first_true`, but with no upper limit. Finds one by doubling the distance from `start`
+// until the predicate is true, so it's quick even if the answer is huge. Gives up if it would
+// overflow.
+pub fn first_true_from<PredFn>(start: i64, pred: PredFn) -> Option<i64>
+where
+    PredFn: Fn(i64) -> bool,
+{
+    if pred(start) {
+        return Some(start);
+    }
+    let mut last_false = start;
+    let mut step: i64 = 1;
+    loop {
+        let next = start.checked_add(step)?;
+        if pred(next) {
+            return first_true(last_false + 1, next + 1, pred);
+        }
+        last_false = next;
+        step = step.checked_mul(2)?;
+    }
+}
+
+// Like `last_true`, with no upper limit. None if the predicate is false at `start`, or is still
+// true when the search runs out of integers.
+pub fn last_true_from<PredFn>(start: i64, pred: PredFn) -> Option<i64>
+where
+    PredFn: Fn(i64) -> bool,
+{
+    if !pred(start) {
+        return None;
+    }
+    first_true_from(start, |n| !pred(n)).map(|first_false| first_false - 1)
+}
+
+// The largest n, starting from `start`, that `cost` says can be afforded with `budget`, where
+// bigger values of n never cost less
+pub fn largest_within_budget<CostFn>(start: i64, budget: i64, cost: CostFn) -> Option<i64>
+where
+    CostFn: Fn(i64) -> i64,
+{
+    last_true_from(start, |n| cost(n) <= budget)
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // bounded {{{2
+    #[test]
+    fn bounded() {
+        assert_eq!(first_true(0, 100, |n| n >= 37), Some(37));
+        assert_eq!(first_true(0, 100, |n| n >= 0), Some(0));
+        assert_eq!(first_true(0, 100, |n| n >= 99), Some(99));
+        assert_eq!(first_true(0, 100, |n| n >= 100), None);
+        assert_eq!(first_true(5, 5, |_| true), None);
+        assert_eq!(last_true(0, 100, |n| n < 37), Some(36));
+        assert_eq!(last_true(0, 100, |n| n < 0), None);
+        assert_eq!(last_true(0, 100, |_| true), Some(99));
+        assert_eq!(last_true(-10, 10, |n| n <= -10), Some(-10));
+    }
+
+    // unbounded {{{2
+    #[test]
+    fn unbounded() {
+        assert_eq!(
+            first_true_from(0, |n| n >= 1_000_000_007),
+            Some(1_000_000_007)
+        );
+        assert_eq!(first_true_from(10, |n| n >= 3), Some(10));
+        assert_eq!(first_true_from(-50, |n| n * n * n >= 1000), Some(10));
+        assert_eq!(first_true_from(0, |_| false), None);
+        assert_eq!(last_true_from(1, |n| n * n <= 1_000_000), Some(1000));
+        assert_eq!(last_true_from(1, |n| n < 1), None);
+        assert_eq!(last_true_from(0, |_| true), None);
+    }
+
+    #[test]
+    fn budget() {
+        // hitting the budget exactly is still affordable, going one over isn't
+        assert_eq!(largest_within_budget(0, 300, |n| n * 3), Some(100));
+        assert_eq!(largest_within_budget(0, 302, |n| n * 3), Some(100));
+        assert_eq!(largest_within_budget(0, 299, |n| n * 3), Some(99));
+        assert_eq!(largest_within_budget(1, 2, |n| n * 3), None);
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::parse::{Input, ParseError};
+use crate::v2::V2;
+
+// A grid of booleans small enough to fit in a single integer, one bit per cell in reading order,
+// so cell (y, x) is bit y * width + x. That makes one step of a cellular automaton a handful of
+// shifts and bitwise operations, rather than a loop over every cell.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitGrid {
+    width: u32,
+    height: u32,
+    bits: u128,
+}
+
+impl BitGrid {
+    pub const MAX_CELLS: u32 = u128::BITS;
+
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self::from_bits(width, height, 0)
+    }
+
+    // Any bits beyond the edge of the grid are ignored
+    pub const fn from_bits(width: u32, height: u32, bits: u128) -> Self {
+        assert!(width * height <= Self::MAX_CELLS, "too many cells");
+        let grid = Self {
+            width,
+            height,
+            bits: 0,
+        };
+        Self {
+            bits: bits & grid.mask(),
+            ..grid
+        }
+    }
+
+    // Reads the same text as `parse_grid`, where `alive` marks the cells that are set
+    pub fn parse(text: &str, alive: char) -> Result<Self, ParseError> {
+        let width = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let height = text.lines().count();
+        if width * height > Self::MAX_CELLS as usize {
+            let message = format!(
+                "a {}x{} grid has more than {} cells",
+                width,
+                height,
+                Self::MAX_CELLS
+            );
+            return Err(Input::new(text).error(text, message));
+        }
+        Ok(crate::parse_grid(text).filter(|&(_, c)| c == alive).fold(
+            Self::new(width as u32, height as u32),
+            |mut grid, (pos, _)| {
+                grid.set(pos, true);
+                grid
+            },
+        ))
+    }
+
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    // The underlying integer. In the puzzles that use these, this is usually the answer.
+    pub const fn bits(&self) -> u128 {
+        self.bits
+    }
+
+    pub const fn count(&self) -> u32 {
+        self.bits.count_ones()
+    }
+
+    pub const fn contains(&self, V2(y, x): V2) -> bool {
+        y >= 0 && x >= 0 && (y as u32) < self.height && (x as u32) < self.width
+    }
+
+    pub const fn get(&self, pos: V2) -> bool {
+        self.contains(pos) && self.bits & self.bit(pos) != 0
+    }
+
+    pub fn set(&mut self, pos: V2, value: bool) {
+        assert!(self.contains(pos), "{:?} is outside the grid", pos);
+        if value {
+            self.bits |= self.bit(pos);
+        } else {
+            self.bits &= !self.bit(pos);
+        }
+    }
+
+    // Every cell that's set, in reading order
+    pub fn positions(&self) -> impl Iterator<Item = V2> + '_ {
+        (0..self.width * self.height)
+            .filter(|&i| self.bits & (1 << i) != 0)
+            .map(|i| V2((i / self.width) as i64, (i % self.width) as i64))
+    }
+
+    const fn bit(&self, V2(y, x): V2) -> u128 {
+        1 << (y as u32 * self.width + x as u32)
+    }
+
+    const fn mask(&self) -> u128 {
+        match self.width * self.height {
+            Self::MAX_CELLS => u128::MAX,
+            cells => (1 << cells) - 1,
+        }
+    }
+
+    const fn with_bits(&self, bits: u128) -> Self {
+        Self::from_bits(self.width, self.height, bits)
+    }
+
+    // neighbours {{{1
+    // Moves every cell by `offset`, dropping any that go over the edge
+    pub const fn shift(&self, V2(dy, dx): V2) -> Self {
+        let mut bits = self.bits;
+        // clear the columns that would wrap round onto the next row first
+        let mut x = 0;
+        while x < self.width as i64 {
+            if x + dx < 0 || x + dx >= self.width as i64 {
+                bits &= !self.column_mask(x as u32);
+            }
+            x += 1;
+        }
+        let distance = dy * self.width as i64 + dx;
+        let bits = if distance.unsigned_abs() >= u128::BITS as u64 {
+            0
+        } else if distance >= 0 {
+            bits << distance
+        } else {
+            bits >> -distance
+        };
+        self.with_bits(bits)
+    }
+
+    const fn column_mask(&self, x: u32) -> u128 {
+        let mut mask = 0;
+        let mut y = 0;
+        while y < self.height {
+            mask |= 1 << (y * self.width + x);
+            y += 1;
+        }
+        mask
+    }
+
+    // The cells with exactly `n` live neighbours, counting up, down, left and right
+    pub fn neighbours_exactly(&self, n: u32) -> Self {
+        let offsets = [V2(-1, 0), V2(1, 0), V2(0, -1), V2(0, 1)];
+        self.count_equals(&offsets, n)
+    }
+
+    // Like `neighbours_exactly`, but counting diagonals too
+    pub fn neighbours8_exactly(&self, n: u32) -> Self {
+        let offsets = [
+            V2(-1, -1),
+            V2(-1, 0),
+            V2(-1, 1),
+            V2(0, -1),
+            V2(0, 1),
+            V2(1, -1),
+            V2(1, 0),
+            V2(1, 1),
+        ];
+        self.count_equals(&offsets, n)
+    }
+
+    // Adds up the shifted copies of the grid one bit at a time, so counts[i] holds bit i of
+    // every cell's total
+    fn count_equals(&self, offsets: &[V2], n: u32) -> Self {
+        let mut counts = [0u128; 4];
+        for &offset in offsets {
+            let mut carry = self.shift(offset).bits;
+            for count in &mut counts {
+                let sum = *count ^ carry;
+                carry &= *count;
+                *count = sum;
+            }
+        }
+        let bits = counts
+            .iter()
+            .enumerate()
+            .fold(u128::MAX, |acc, (i, &count)| {
+                acc & if n & (1 << i) != 0 { count } else { !count }
+            });
+        self.with_bits(if n < 16 { bits } else { 0 })
+    }
+}
+
+// operators {{{1
+impl BitAnd for BitGrid {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.with_bits(self.bits & rhs.bits)
+    }
+}
+
+impl BitOr for BitGrid {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.with_bits(self.bits | rhs.bits)
+    }
+}
+
+impl BitXor for BitGrid {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        self.with_bits(self.bits ^ rhs.bits)
+    }
+}
+
+impl Not for BitGrid {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.with_bits(!self.bits)
+    }
+}
+
+impl fmt::Debug for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BitGrid({}x{})\n{}", self.width, self.height, self)
+    }
+}
+
+// The same format `parse` reads, with `#` for cells that are set
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height as i64 {
+            for x in 0..self.width as i64 {
+                write!(f, "{}", if self.get(V2(y, x)) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &str = ".#...
+..#..
+###..
+.....
+.....
+";
+
+    #[test]
+    fn parse_and_display() {
+        let grid = BitGrid::parse(GLIDER, '#').unwrap();
+        assert_eq!((grid.width(), grid.height()), (5, 5));
+        assert_eq!(grid.count(), 5);
+        assert!(grid.get(V2(0, 1)));
+        assert!(!grid.get(V2(0, 0)));
+        assert!(!grid.get(V2(-1, 0)));
+        assert_eq!(grid.bits(), 0b111_00100_00010);
+        assert_eq!(grid.to_string(), GLIDER);
+        let positions: Vec<_> = grid.positions().collect();
+        assert_eq!(positions[..2], [V2(0, 1), V2(1, 2)]);
+        let too_big = ".".repeat(12) + "\n";
+        let error = BitGrid::parse(&too_big.repeat(11), '#').unwrap_err();
+        assert_eq!(error.message, "a 12x11 grid has more than 128 cells");
+    }
+
+    #[test]
+    fn shifts() {
+        let grid = BitGrid::parse(GLIDER, '#').unwrap();
+        assert_eq!(
+            grid.shift(V2(0, 3)).to_string(),
+            "....#\n.....\n...##\n.....\n.....\n"
+        );
+        assert_eq!(
+            grid.shift(V2(0, -1)).to_string(),
+            "#....\n.#...\n##...\n.....\n.....\n"
+        );
+        assert_eq!(grid.shift(V2(3, 0)).count(), 2);
+        assert_eq!(
+            grid.shift(V2(-1, -1)).to_string(),
+            ".#...\n##...\n.....\n.....\n.....\n"
+        );
+        assert_eq!(grid.shift(V2(0, 100)).count(), 0);
+    }
+
+    #[test]
+    fn game_of_life() {
+        let mut grid = BitGrid::parse(GLIDER, '#').unwrap();
+        for _ in 0..4 {
+            let survives = grid & (grid.neighbours8_exactly(2) | grid.neighbours8_exactly(3));
+            let born = !grid & grid.neighbours8_exactly(3);
+            grid = survives | born;
+        }
+        // after four steps, a glider has moved one down and one to the right
+        assert_eq!(grid, BitGrid::parse(GLIDER, '#').unwrap().shift(V2(1, 1)));
+    }
+
+    #[test]
+    fn full_size() {
+        let grid = !BitGrid::new(16, 8);
+        assert_eq!(grid.count(), 128);
+        assert_eq!(grid.neighbours_exactly(4).count(), 14 * 6);
+        assert_eq!(grid.neighbours_exactly(2).count(), 4);
+        assert_eq!(grid.neighbours8_exactly(8).count(), 14 * 6);
+    }
+}
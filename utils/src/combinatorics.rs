@@ -0,0 +1,197 @@
+// Lazy iterators over arrangements of a slice. Each one only keeps a handful of indices around
+// between steps, and clones out the items for the arrangement it's yielding, so a search can
+// stop as soon as it finds what it's after.
+
+// permutations {{{1
+// Every ordering of `items`, using Heap's algorithm, which gets from one permutation to the next
+// with a single swap
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<T> {
+    Permutations {
+        items: items.to_vec(),
+        counters: vec![0; items.len()],
+        i: 0,
+        started: false,
+    }
+}
+
+pub struct Permutations<T> {
+    items: Vec<T>,
+    counters: Vec<usize>,
+    i: usize,
+    started: bool,
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            self.i = 1;
+            return Some(self.items.clone());
+        }
+        while self.i < self.items.len() {
+            let i = self.i;
+            if self.counters[i] < i {
+                let j = if i.is_multiple_of(2) {
+                    0
+                } else {
+                    self.counters[i]
+                };
+                self.items.swap(j, i);
+                self.counters[i] += 1;
+                self.i = 1;
+                return Some(self.items.clone());
+            }
+            self.counters[i] = 0;
+            self.i += 1;
+        }
+        None
+    }
+}
+
+// combinations {{{1
+// Every k-subset of `items`, keeping them in their original order, with the subsets in
+// lexicographic order of position
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        items,
+        indices: (k <= items.len()).then(|| (0..k).collect()),
+    }
+}
+
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    // the positions of the next combination, or None once we've run out
+    indices: Option<Vec<usize>>,
+}
+
+impl<T: Clone> Iterator for Combinations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.indices.as_mut()?;
+        let result = indices.iter().map(|&i| self.items[i].clone()).collect();
+        // find the rightmost index that can still move right, then reset everything after it
+        let n = self.items.len();
+        let k = indices.len();
+        match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..k {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+            None => self.indices = None,
+        }
+        Some(result)
+    }
+}
+
+// Every subset of `items`, of every size, starting with the empty one
+pub fn subsets<T: Clone>(items: &[T]) -> impl Iterator<Item = Vec<T>> + '_ {
+    (0..=items.len()).flat_map(move |k| combinations(items, k))
+}
+
+// cartesian product {{{1
+// Every way of picking one item from each of `sets`, like nested for loops with the last set
+// innermost
+pub fn cartesian_product<T: Clone>(sets: &[Vec<T>]) -> CartesianProduct<'_, T> {
+    let empty = sets.iter().any(Vec::is_empty);
+    CartesianProduct {
+        sets,
+        indices: (!empty).then(|| vec![0; sets.len()]),
+    }
+}
+
+pub struct CartesianProduct<'a, T> {
+    sets: &'a [Vec<T>],
+    indices: Option<Vec<usize>>,
+}
+
+impl<T: Clone> Iterator for CartesianProduct<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.indices.as_mut()?;
+        let result = indices
+            .iter()
+            .zip(self.sets)
+            .map(|(&i, set)| set[i].clone())
+            .collect();
+        // count up like an odometer
+        let mut position = indices.len();
+        loop {
+            if position == 0 {
+                self.indices = None;
+                break;
+            }
+            position -= 1;
+            indices[position] += 1;
+            if indices[position] < self.sets[position].len() {
+                break;
+            }
+            indices[position] = 0;
+        }
+        Some(result)
+    }
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    // permutations {{{2
+    #[test]
+    fn permutations_test() {
+        assert_eq!(permutations(&[0; 0]).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(permutations(&[1]).collect::<Vec<_>>(), vec![vec![1]]);
+        let expected = vec![
+            vec![1, 2, 3],
+            vec![2, 1, 3],
+            vec![3, 1, 2],
+            vec![1, 3, 2],
+            vec![2, 3, 1],
+            vec![3, 2, 1],
+        ];
+        assert_eq!(permutations(&[1, 2, 3]).collect::<Vec<_>>(), expected);
+        let all: HashSet<_> = permutations(&[1, 2, 3, 4, 5]).collect();
+        assert_eq!(all.len(), 120);
+        assert_eq!(permutations(&[1, 2, 3, 4, 5, 6]).count(), 720);
+    }
+
+    // combinations {{{2
+    #[test]
+    fn combinations_test() {
+        let expected = vec![
+            vec!['a', 'b'],
+            vec!['a', 'c'],
+            vec!['a', 'd'],
+            vec!['b', 'c'],
+            vec!['b', 'd'],
+            vec!['c', 'd'],
+        ];
+        let items = ['a', 'b', 'c', 'd'];
+        assert_eq!(combinations(&items, 2).collect::<Vec<_>>(), expected);
+        assert_eq!(combinations(&items, 0).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(combinations(&items, 4).count(), 1);
+        assert_eq!(combinations(&items, 5).count(), 0);
+        assert_eq!(subsets(&items).count(), 16);
+        assert_eq!(subsets(&items).nth(1), Some(vec!['a']));
+    }
+
+    // cartesian product {{{2
+    #[test]
+    fn cartesian_product_test() {
+        let sets = vec![vec![1, 2], vec![3], vec![4, 5]];
+        let expected = vec![vec![1, 3, 4], vec![1, 3, 5], vec![2, 3, 4], vec![2, 3, 5]];
+        assert_eq!(cartesian_product(&sets).collect::<Vec<_>>(), expected);
+        assert_eq!(
+            cartesian_product::<i32>(&[]).collect::<Vec<_>>(),
+            vec![vec![]]
+        );
+        assert_eq!(cartesian_product(&[vec![1], vec![]]).count(), 0);
+    }
+}
@@ -0,0 +1,188 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::v2::V2;
+
+// Points with named components, for code where mixing up the axes is easy. `RowCol` uses screen
+// conventions, where rows count downwards from the top, and `XY` uses Cartesian ones, where y
+// counts upwards. The only way to get from one to the other is an explicit conversion.
+
+// row/col {{{1
+// Ordered row by row, i.e. in reading order
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RowCol {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl RowCol {
+    pub const fn new(row: i64, col: i64) -> Self {
+        Self { row, col }
+    }
+
+    // Many puzzles print screen coordinates as `x,y`, with y still counting downwards
+    pub const fn from_screen_xy(x: i64, y: i64) -> Self {
+        Self { row: y, col: x }
+    }
+
+    pub const fn to_cartesian(self) -> XY {
+        XY {
+            x: self.col,
+            y: -self.row,
+        }
+    }
+
+    // Up, down, left and right, in that order
+    pub const fn neighbours(self) -> [Self; 4] {
+        let Self { row, col } = self;
+        [
+            Self::new(row - 1, col),
+            Self::new(row + 1, col),
+            Self::new(row, col - 1),
+            Self::new(row, col + 1),
+        ]
+    }
+
+    pub const fn manhattan_distance(self, other: Self) -> i64 {
+        (other.row - self.row).abs() + (other.col - self.col).abs()
+    }
+}
+
+// V2 is used as (y, x) throughout, which is the same as (row, col)
+impl From<V2> for RowCol {
+    fn from(V2(row, col): V2) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<RowCol> for V2 {
+    fn from(RowCol { row, col }: RowCol) -> Self {
+        Self(row, col)
+    }
+}
+
+impl Add for RowCol {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.row + other.row, self.col + other.col)
+    }
+}
+
+impl AddAssign for RowCol {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for RowCol {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl SubAssign for RowCol {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+// x/y {{{1
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct XY {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl XY {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub const fn to_screen(self) -> RowCol {
+        RowCol {
+            row: -self.y,
+            col: self.x,
+        }
+    }
+
+    // Up, down, left and right, in that order
+    pub const fn neighbours(self) -> [Self; 4] {
+        let Self { x, y } = self;
+        [
+            Self::new(x, y + 1),
+            Self::new(x, y - 1),
+            Self::new(x - 1, y),
+            Self::new(x + 1, y),
+        ]
+    }
+
+    pub const fn manhattan_distance(self, other: Self) -> i64 {
+        (other.x - self.x).abs() + (other.y - self.y).abs()
+    }
+}
+
+impl Add for XY {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for XY {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for XY {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for XY {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let pos = RowCol::new(2, 5);
+        assert_eq!(RowCol::from_screen_xy(5, 2), pos);
+        assert_eq!(V2::from(pos), V2(2, 5));
+        assert_eq!(RowCol::from(V2(2, 5)), pos);
+        assert_eq!(pos.to_cartesian(), XY::new(5, -2));
+        assert_eq!(pos.to_cartesian().to_screen(), pos);
+    }
+
+    #[test]
+    fn neighbours_agree() {
+        // Going up on the screen is going up in Cartesian coordinates too
+        let pos = RowCol::new(3, 4);
+        let screen = pos.neighbours().map(RowCol::to_cartesian);
+        assert_eq!(screen, pos.to_cartesian().neighbours());
+        assert_eq!(pos.manhattan_distance(RowCol::new(0, 0)), 7);
+        assert_eq!(XY::new(-1, 2).manhattan_distance(XY::new(1, 0)), 4);
+    }
+
+    #[test]
+    fn reading_order() {
+        let mut points = vec![RowCol::new(1, 0), RowCol::new(0, 5), RowCol::new(0, 1)];
+        points.sort();
+        assert_eq!(
+            points,
+            vec![RowCol::new(0, 1), RowCol::new(0, 5), RowCol::new(1, 0)]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::v2::V2;
+
+// The shortest way through the maze between two points of interest, along with every item found
+// on the way (not counting the ones at either end)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Corridor<Item> {
+    pub distance: usize,
+    pub items: BTreeSet<Item>,
+}
+
+// Compresses a grid maze into a graph, where each point of interest is connected to every other
+// one that it can reach without passing through a third. Other points of interest along the way
+// can be reached via a chain of edges instead, which is never any longer.
+pub fn contract_grid<PassableFn>(
+    passable: PassableFn,
+    points: &HashSet<V2>,
+) -> HashMap<V2, HashMap<V2, usize>>
+where
+    PassableFn: Fn(&V2) -> bool,
+{
+    contract_grid_with_items(passable, |_| None::<()>, points)
+        .into_iter()
+        .map(|(from, edges)| {
+            let edges = edges
+                .into_iter()
+                .map(|(to, corridor)| (to, corridor.distance))
+                .collect();
+            (from, edges)
+        })
+        .collect()
+}
+
+// Like `contract_grid`, but also records whatever `item_at` finds along each corridor, such as
+// doors that need to be unlocked.
+pub fn contract_grid_with_items<PassableFn, ItemFn, Item>(
+    passable: PassableFn,
+    item_at: ItemFn,
+    points: &HashSet<V2>,
+) -> HashMap<V2, HashMap<V2, Corridor<Item>>>
+where
+    PassableFn: Fn(&V2) -> bool,
+    ItemFn: Fn(&V2) -> Option<Item>,
+    Item: Ord + Clone,
+{
+    points
+        .iter()
+        .map(|&start| (start, corridors_from(&passable, &item_at, points, start)))
+        .collect()
+}
+
+fn corridors_from<PassableFn, ItemFn, Item>(
+    passable: PassableFn,
+    item_at: ItemFn,
+    points: &HashSet<V2>,
+    start: V2,
+) -> HashMap<V2, Corridor<Item>>
+where
+    PassableFn: Fn(&V2) -> bool,
+    ItemFn: Fn(&V2) -> Option<Item>,
+    Item: Ord + Clone,
+{
+    // the items crossed on the way to each position we've found
+    let mut items_on_route = HashMap::from([(start, BTreeSet::new())]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    let mut result = HashMap::new();
+    while let Some((pos, distance)) = queue.pop_front() {
+        if pos != start && points.contains(&pos) {
+            let items = items_on_route[&pos].clone();
+            result.insert(pos, Corridor { distance, items });
+            continue;
+        }
+        let mut items = items_on_route[&pos].clone();
+        if pos != start {
+            items.extend(item_at(&pos));
+        }
+        for neighbour in pos.taxicab_neighbours() {
+            if passable(&neighbour) && !items_on_route.contains_key(&neighbour) {
+                items_on_route.insert(neighbour, items.clone());
+                queue.push_back((neighbour, distance + 1));
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "#########
+#a..B..b#
+#.#####.#
+#...c...#
+#########";
+
+    fn maze() -> HashMap<V2, char> {
+        crate::parse_grid(MAZE).collect()
+    }
+
+    fn points_of_interest(maze: &HashMap<V2, char>) -> HashSet<V2> {
+        maze.iter()
+            .filter(|(_, &c)| c.is_ascii_lowercase())
+            .map(|(&pos, _)| pos)
+            .collect()
+    }
+
+    #[test]
+    fn contract_grid_test() {
+        let maze = maze();
+        let points = points_of_interest(&maze);
+        let graph = contract_grid(|pos| matches!(maze.get(pos), Some(c) if *c != '#'), &points);
+        let a = V2(1, 1);
+        let b = V2(1, 7);
+        let c = V2(3, 4);
+        assert_eq!(graph[&a], HashMap::from([(b, 6), (c, 5)]));
+        assert_eq!(graph[&b], HashMap::from([(a, 6), (c, 5)]));
+        assert_eq!(graph[&c], HashMap::from([(a, 5), (b, 5)]));
+    }
+
+    #[test]
+    fn contract_grid_with_items_test() {
+        let maze = maze();
+        let points = points_of_interest(&maze);
+        let passable = |pos: &V2| matches!(maze.get(pos), Some(c) if *c != '#');
+        let door_at = |pos: &V2| maze.get(pos).filter(|c| c.is_ascii_uppercase()).copied();
+        let graph = contract_grid_with_items(passable, door_at, &points);
+        let a = V2(1, 1);
+        let b = V2(1, 7);
+        let c = V2(3, 4);
+        assert_eq!(graph[&a][&b].items, BTreeSet::from(['B']));
+        assert_eq!(graph[&a][&c].items, BTreeSet::new());
+        assert_eq!(graph[&c][&b].distance, 5);
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Where the sequence start, f(start), f(f(start)), ... starts repeating itself. The state after
+// `start` steps is the first one that comes round again, `length` steps later. All of the
+// functions here loop forever if the sequence never repeats.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    // The earliest step whose state is the same as the state after `n` steps
+    pub const fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+// floyd {{{1
+// The tortoise and the hare: only needs to keep two states around at once
+pub fn floyd<T, F>(start: T, f: F) -> Cycle
+where
+    T: PartialEq + Clone,
+    F: Fn(&T) -> T,
+{
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&f(&hare));
+    }
+    // the distance between them is now a multiple of the cycle length, so walking the tortoise
+    // from the start and the hare from where it is means they meet at the start of the cycle
+    let mut cycle_start = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        cycle_start += 1;
+    }
+    let mut length = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+// brent {{{1
+// Like `floyd`, but usually calls `f` fewer times
+pub fn brent<T, F>(start: T, f: F) -> Cycle
+where
+    T: PartialEq + Clone,
+    F: Fn(&T) -> T,
+{
+    // find the length by teleporting the tortoise to the hare at every power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+    // then start the hare `length` steps ahead, so they meet at the start of the cycle
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        cycle_start += 1;
+    }
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+// hashing {{{1
+// Remembers every state, so it only ever calls `f` once per state. Also returns those states, in
+// order, up to the end of the first time round the cycle.
+pub fn find_cycle<T, F>(start: T, f: F) -> (Cycle, Vec<T>)
+where
+    T: Hash + Eq + Clone,
+    F: Fn(&T) -> T,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+    loop {
+        if let Some(&cycle_start) = seen.get(&state) {
+            let length = states.len() - cycle_start;
+            let cycle = Cycle {
+                start: cycle_start,
+                length,
+            };
+            return (cycle, states);
+        }
+        seen.insert(state.clone(), states.len());
+        let next = f(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+// The state after `n` steps, skipping over all the trips round the cycle
+pub fn nth_state<T, F>(start: T, f: F, n: usize) -> T
+where
+    T: Hash + Eq + Clone,
+    F: Fn(&T) -> T,
+{
+    let (cycle, mut states) = find_cycle(start, f);
+    states.swap_remove(cycle.equivalent_step(n))
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 -> ...
+    fn step(&n: &u32) -> u32 {
+        if n == 6 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    const EXPECTED: Cycle = Cycle {
+        start: 3,
+        length: 4,
+    };
+
+    #[test]
+    fn algorithms_agree() {
+        assert_eq!(floyd(0, step), EXPECTED);
+        assert_eq!(brent(0, step), EXPECTED);
+        let (cycle, states) = find_cycle(0, step);
+        assert_eq!(cycle, EXPECTED);
+        assert_eq!(states, vec![0, 1, 2, 3, 4, 5, 6]);
+        // a sequence that's a cycle from the very start
+        let pure = Cycle {
+            start: 0,
+            length: 4,
+        };
+        assert_eq!(floyd(3, step), pure);
+        assert_eq!(brent(3, step), pure);
+        assert_eq!(find_cycle(3, step).0, pure);
+        // and one that repeats immediately
+        let fixed = Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(floyd(5, |&n| n), fixed);
+        assert_eq!(brent(5, |&n| n), fixed);
+    }
+
+    #[test]
+    fn nth_state_test() {
+        assert_eq!(EXPECTED.equivalent_step(2), 2);
+        assert_eq!(EXPECTED.equivalent_step(7), 3);
+        assert_eq!(EXPECTED.equivalent_step(1_000_000_002), 6);
+        assert_eq!(nth_state(0, step, 2), 2);
+        assert_eq!(nth_state(0, step, 1_000_000_002), 6);
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+    ops::Add,
+};
+
+// dijkstra {{{1
+// Like `bfs`, but every neighbour comes with the cost of moving to it. Returns the cheapest path
+// to the first node satisfying `done`, along with its total cost.
+pub fn dijkstra<Node, Cost, NeighbourFn, DoneFn>(
+    neighbours: NeighbourFn,
+    done: DoneFn,
+    start: Node,
+) -> Option<(Vec<Node>, Cost)>
+where
+    NeighbourFn: Fn(&Node) -> Vec<(Node, Cost)>,
+    DoneFn: Fn(&Node) -> bool,
+    Node: Hash + Eq + Clone,
+    Cost: Copy + Ord + Add<Output = Cost> + Default,
+{
+    astar(neighbours, |_| Cost::default(), done, start)
+}
+
+// The cheapest cost of getting to every node reachable from `start`
+pub fn dijkstra_distances<Node, Cost, NeighbourFn>(
+    neighbours: NeighbourFn,
+    start: Node,
+) -> HashMap<Node, Cost>
+where
+    NeighbourFn: Fn(&Node) -> Vec<(Node, Cost)>,
+    Node: Hash + Eq + Clone,
+    Cost: Copy + Ord + Add<Output = Cost> + Default,
+{
+    let mut search = Search::new(start, Cost::default());
+    while search.next(&neighbours, |_| Cost::default()).is_some() {}
+    search.best
+}
+
+// astar {{{1
+// Dijkstra, guided by `heuristic`, an estimate of the remaining cost from a node to the goal. As
+// long as it never overestimates, and never drops by more than the cost of an edge, the path
+// found is still the cheapest.
+pub fn astar<Node, Cost, NeighbourFn, HeuristicFn, DoneFn>(
+    neighbours: NeighbourFn,
+    heuristic: HeuristicFn,
+    done: DoneFn,
+    start: Node,
+) -> Option<(Vec<Node>, Cost)>
+where
+    NeighbourFn: Fn(&Node) -> Vec<(Node, Cost)>,
+    HeuristicFn: Fn(&Node) -> Cost,
+    DoneFn: Fn(&Node) -> bool,
+    Node: Hash + Eq + Clone,
+    Cost: Copy + Ord + Add<Output = Cost> + Default,
+{
+    let mut search = Search::new(start.clone(), heuristic(&start));
+    while let Some(node) = search.next(&neighbours, &heuristic) {
+        if done(&node) {
+            let cost = search.best[&node];
+            let mut result = Vec::from([node.clone()]);
+            let mut node = node;
+            while let Some(pred) = search.predecessors.get(&node) {
+                result.push(pred.clone());
+                node = pred.clone();
+            }
+            result.reverse();
+            return Some((result, cost));
+        }
+    }
+    None
+}
+
+// search state {{{1
+// The heap holds indices into `entries` rather than nodes themselves, so that nodes don't need
+// to be `Ord`. Ties are broken by the order nodes were discovered in.
+struct Search<Node, Cost> {
+    entries: Vec<Node>,
+    heap: BinaryHeap<Reverse<(Cost, usize)>>,
+    best: HashMap<Node, Cost>,
+    predecessors: HashMap<Node, Node>,
+    visited: HashSet<Node>,
+}
+
+impl<Node, Cost> Search<Node, Cost>
+where
+    Node: Hash + Eq + Clone,
+    Cost: Copy + Ord + Add<Output = Cost> + Default,
+{
+    fn new(start: Node, priority: Cost) -> Self {
+        Self {
+            entries: Vec::from([start.clone()]),
+            heap: BinaryHeap::from([Reverse((priority, 0))]),
+            best: HashMap::from([(start, Cost::default())]),
+            predecessors: HashMap::new(),
+            visited: HashSet::new(),
+        }
+    }
+
+    // Visits the next cheapest node, returning it once its neighbours have been queued
+    fn next<NeighbourFn, HeuristicFn>(
+        &mut self,
+        neighbours: NeighbourFn,
+        heuristic: HeuristicFn,
+    ) -> Option<Node>
+    where
+        NeighbourFn: Fn(&Node) -> Vec<(Node, Cost)>,
+        HeuristicFn: Fn(&Node) -> Cost,
+    {
+        while let Some(Reverse((_, i))) = self.heap.pop() {
+            let node = self.entries[i].clone();
+            if !self.visited.insert(node.clone()) {
+                continue;
+            }
+            let cost = self.best[&node];
+            for (neighbour, edge_cost) in neighbours(&node) {
+                let new_cost = cost + edge_cost;
+                let is_cheaper = self
+                    .best
+                    .get(&neighbour)
+                    .is_none_or(|&current| new_cost < current);
+                if is_cheaper {
+                    self.best.insert(neighbour.clone(), new_cost);
+                    self.predecessors.insert(neighbour.clone(), node.clone());
+                    let priority = new_cost + heuristic(&neighbour);
+                    self.heap.push(Reverse((priority, self.entries.len())));
+                    self.entries.push(neighbour);
+                }
+            }
+            return Some(node);
+        }
+        None
+    }
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::V2;
+
+    // A small weighted graph:
+    //   a -1-> b -1-> c -1-> d
+    //   a -------5----------> d
+    //   a -2-> e
+    fn graph(node: &char) -> Vec<(char, usize)> {
+        match node {
+            'a' => vec![('b', 1), ('d', 5), ('e', 2)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        }
+    }
+
+    // dijkstra {{{2
+    #[test]
+    fn dijkstra_test() {
+        let result = dijkstra(graph, |&n| n == 'd', 'a');
+        assert_eq!(result, Some((vec!['a', 'b', 'c', 'd'], 3)));
+        assert_eq!(dijkstra(graph, |&n| n == 'a', 'a'), Some((vec!['a'], 0)));
+        assert_eq!(dijkstra(graph, |&n| n == 'z', 'a'), None);
+    }
+
+    #[test]
+    fn dijkstra_distances_test() {
+        let distances = dijkstra_distances(graph, 'a');
+        let expected = HashMap::from([('a', 0), ('b', 1), ('c', 2), ('d', 3), ('e', 2)]);
+        assert_eq!(distances, expected);
+    }
+
+    // astar {{{2
+    #[test]
+    fn astar_test() {
+        // An open 10x10 grid with a wall down the middle, where each step costs 1
+        let is_open =
+            |&V2(y, x): &V2| (0..10).contains(&y) && (0..10).contains(&x) && !(x == 5 && y < 8);
+        let neighbours = |pos: &V2| {
+            pos.taxicab_neighbours()
+                .into_iter()
+                .filter(is_open)
+                .map(|p| (p, 1))
+                .collect()
+        };
+        let goal = V2(0, 9);
+        let heuristic = |pos: &V2| pos.manhattan_distance(goal);
+        let (path, cost) = astar(neighbours, heuristic, |&p| p == goal, V2(0, 0)).unwrap();
+        assert_eq!(cost, 8 + 9 + 8);
+        assert_eq!(path.len() as i64, cost + 1);
+        assert!(path.windows(2).all(|w| w[0].manhattan_distance(w[1]) == 1));
+        let (_, dijkstra_cost) = dijkstra(neighbours, |&p| p == goal, V2(0, 0)).unwrap();
+        assert_eq!(cost, dijkstra_cost);
+    }
+}
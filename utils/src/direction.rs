@@ -0,0 +1,119 @@
+use crate::v2::V2;
+
+// One of the four directions on a grid. Converting to a V2 follows the (y, x) convention used by
+// `parse_grid`, so `Up` decreases y.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // In clockwise order, starting from `Up`
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Right => Self::Up,
+            Self::Down => Self::Right,
+            Self::Left => Self::Down,
+        }
+    }
+
+    pub const fn turn_around(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Right => Self::Left,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+        }
+    }
+
+    pub const fn to_v2(self) -> V2 {
+        match self {
+            Self::Up => V2(-1, 0),
+            Self::Right => V2(0, 1),
+            Self::Down => V2(1, 0),
+            Self::Left => V2(0, -1),
+        }
+    }
+
+    // Understands both `U`, `D`, `L`, `R` and arrows drawn as `^`, `v`, `<`, `>`
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | '^' => Some(Self::Up),
+            'R' | '>' => Some(Self::Right),
+            'D' | 'v' => Some(Self::Down),
+            'L' | '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_char(c).ok_or(c)
+    }
+}
+
+impl From<Direction> for V2 {
+    fn from(direction: Direction) -> Self {
+        direction.to_v2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for direction in Direction::all() {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.turn_around());
+            assert_eq!(direction.turn_left().turn_left(), direction.turn_around());
+            assert_eq!(
+                direction.to_v2() + direction.turn_around().to_v2(),
+                V2(0, 0)
+            );
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+
+    #[test]
+    fn parsing() {
+        let parsed: Vec<_> = "URDL".chars().map(Direction::from_char).collect();
+        let arrows: Vec<_> = "^>v<".chars().map(Direction::from_char).collect();
+        let expected: Vec<_> = Direction::all().map(Some).collect();
+        assert_eq!(parsed, expected);
+        assert_eq!(arrows, expected);
+        assert_eq!(Direction::try_from('x'), Err('x'));
+    }
+
+    #[test]
+    fn vectors() {
+        assert_eq!(V2::from(Direction::Up), V2(-1, 0));
+        assert_eq!(Direction::Right.to_v2(), V2(0, 1));
+        let total = Direction::all().fold(V2(0, 0), |acc, d| acc + d.to_v2());
+        assert_eq!(total, V2(0, 0));
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::v2::V2;
+
+// A dense, rectangular grid, indexed by V2 in (y, x) format like everything else
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "wrong number of cells");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // Panics if the lines aren't all the same length
+    pub fn parse<F>(input: &str, f: F) -> Self
+    where
+        F: Fn(char) -> T,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.lines() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&f));
+            let line_width = cells.len() - before;
+            assert_eq!(*width.get_or_insert(line_width), line_width, "ragged grid");
+            height += 1;
+        }
+        Self::from_vec(width.unwrap_or(0), height, cells)
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub const fn contains(&self, V2(y, x): V2) -> bool {
+        y >= 0 && x >= 0 && (y as usize) < self.height && (x as usize) < self.width
+    }
+
+    fn offset(&self, pos: V2) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.0 as usize * self.width + pos.1 as usize)
+    }
+
+    pub fn get(&self, pos: V2) -> Option<&T> {
+        self.offset(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: V2) -> Option<&mut T> {
+        self.offset(pos).map(|i| &mut self.cells[i])
+    }
+
+    // Every position in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = V2> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        (0..height).flat_map(move |y| (0..width).map(move |x| V2(y, x)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (V2, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    // The positions up, down, left and right of `pos` that are inside the grid
+    pub fn neighbours(&self, pos: V2) -> impl Iterator<Item = V2> + '_ {
+        pos.taxicab_neighbours()
+            .into_iter()
+            .filter(|&p| self.contains(p))
+    }
+
+    // Like `neighbours`, but including diagonals
+    pub fn neighbours8(&self, V2(y, x): V2) -> impl Iterator<Item = V2> + '_ {
+        (-1..=1)
+            .flat_map(move |dy| (-1..=1).map(move |dx| V2(y + dy, x + dx)))
+            .filter(move |&p| p != V2(y, x) && self.contains(p))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Grid::from_vec(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self::from_vec(width, height, vec![fill; width * height])
+    }
+
+    // Builds a new grid of the given size, where each cell is copied from `source(y, x)`
+    fn remap<F>(&self, width: usize, height: usize, source: F) -> Self
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (y, x)))
+            .map(|(y, x)| {
+                let (sy, sx) = source(y, x);
+                self.cells[sy * self.width + sx].clone()
+            })
+            .collect();
+        Self::from_vec(width, height, cells)
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |y, x| (x, y))
+    }
+
+    // Mirrors the grid left to right
+    pub fn flip_horizontal(&self) -> Self {
+        self.remap(self.width, self.height, |y, x| (y, self.width - 1 - x))
+    }
+
+    // Mirrors the grid top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.remap(self.width, self.height, |y, x| (self.height - 1 - y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        self.remap(self.height, self.width, |y, x| (self.height - 1 - x, y))
+    }
+
+    pub fn rotate_anticlockwise(&self) -> Self {
+        self.remap(self.height, self.width, |y, x| (x, self.width - 1 - y))
+    }
+}
+
+impl<T> Index<V2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: V2) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T> IndexMut<V2> for Grid<T> {
+    fn index_mut(&mut self, pos: V2) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "#..
+.#.
+...
+##.
+";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(TEXT, |c| c)
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid[V2(1, 1)], '#');
+        assert_eq!(grid[V2(3, 0)], '#');
+        assert_eq!(grid.to_string(), TEXT);
+        let bools = Grid::parse(TEXT, |c| c == '#');
+        assert_eq!(bools.iter().filter(|(_, &b)| b).count(), 4);
+    }
+
+    #[test]
+    fn bounds() {
+        let mut grid = grid();
+        assert_eq!(grid.get(V2(-1, 0)), None);
+        assert_eq!(grid.get(V2(0, 3)), None);
+        assert_eq!(grid.get(V2(4, 0)), None);
+        *grid.get_mut(V2(2, 2)).unwrap() = '#';
+        assert_eq!(grid.row(2), &['.', '.', '#']);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds_index() {
+        let _ = grid()[V2(0, 5)];
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        let mut corner: Vec<_> = grid.neighbours(V2(0, 0)).collect();
+        corner.sort();
+        assert_eq!(corner, vec![V2(0, 1), V2(1, 0)]);
+        assert_eq!(grid.neighbours(V2(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(V2(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(V2(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(V2(3, 2)).count(), 3);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        let rows: Vec<String> = grid.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, vec!["#..", ".#.", "...", "##."]);
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, vec!["#..#", ".#.#", "...."]);
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "#..#\n.#.#\n....\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "..#\n.#.\n...\n.##\n");
+        assert_eq!(grid.flip_vertical().to_string(), "##.\n...\n.#.\n#..\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "#..#\n#.#.\n....\n");
+        assert_eq!(
+            grid.rotate_anticlockwise().to_string(),
+            "....\n.#.#\n#..#\n"
+        );
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn map_and_new() {
+        let grid = Grid::new(2, 2, 0).map(|n| n + 1);
+        assert_eq!(grid.to_string(), "11\n11\n");
+    }
+}
@@ -1,24 +1,23 @@
 pub mod bfs;
+pub mod bisect;
+pub mod bitgrid;
+pub mod combinatorics;
+pub mod coords;
+pub mod corridors;
+pub mod cycle;
+pub mod dijkstra;
+pub mod direction;
+pub mod grid;
+pub mod math;
+pub mod ocr;
+pub mod parse;
+pub mod render;
+pub mod solution;
 pub mod v2;
 pub mod v3;
+pub mod vecn;
 
-pub const fn gcd(a: i64, b: i64) -> i64 {
-    let mut a = a.abs();
-    let mut b = b.abs();
-    if a == 0 {
-        return b;
-    } else if b == 0 {
-        return a;
-    }
-    while a != b {
-        if a > b {
-            a -= b;
-        } else {
-            b -= a;
-        }
-    }
-    a
-}
+pub use math::{gcd, lcm};
 
 pub fn parse_grid(input: &str) -> impl Iterator<Item = (v2::V2, char)> + '_ {
     input.lines().enumerate().flat_map(|(y, line)| {
@@ -28,10 +27,6 @@ pub fn parse_grid(input: &str) -> impl Iterator<Item = (v2::V2, char)> + '_ {
     })
 }
 
-pub const fn lcm(a: i64, b: i64) -> i64 {
-    (a * b) / gcd(a, b)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,154 @@
+// Number theory, mostly for puzzles that do everything modulo some huge number. Moduli are
+// always expected to be positive, and results are always in 0..modulus.
+
+// gcd and lcm {{{1
+pub const fn gcd(a: i64, b: i64) -> i64 {
+    let mut a = a.abs();
+    let mut b = b.abs();
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Returns (g, x, y) such that a * x + b * y = g = gcd(a, b)
+pub const fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// Divides before multiplying, so this only overflows if the answer itself doesn't fit
+pub const fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+pub const fn checked_lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    match (a / gcd(a, b)).checked_mul(b) {
+        Some(n) => n.checked_abs(),
+        None => None,
+    }
+}
+
+// modular arithmetic {{{1
+// a * b % modulus, without overflowing even when the modulus is close to i64::MAX
+pub const fn mulmod(a: i64, b: i64, modulus: i64) -> i64 {
+    let a = a.rem_euclid(modulus) as u128;
+    let b = b.rem_euclid(modulus) as u128;
+    (a * b % modulus as u128) as i64
+}
+
+// base ^ exponent % modulus, by repeated squaring
+pub const fn modpow(base: i64, mut exponent: u64, modulus: i64) -> i64 {
+    let mut base = base.rem_euclid(modulus);
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mulmod(result, base, modulus);
+        }
+        base = mulmod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+// The x such that a * x = 1 (mod modulus), if there is one
+pub const fn modinv(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    if g == 1 {
+        Some(x.rem_euclid(modulus))
+    } else {
+        None
+    }
+}
+
+// chinese remainder theorem {{{1
+// Given a list of (remainder, modulus) pairs, finds the smallest non-negative x that has each
+// remainder for its modulus, along with the lcm of the moduli, which is how often it repeats.
+// The moduli don't need to be coprime, but if they disagree then there's no answer.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, m), &(remainder, modulus)| {
+            let (g, p, _) = extended_gcd(m, modulus);
+            let difference = remainder - x;
+            if difference % g != 0 {
+                return None;
+            }
+            let combined = checked_lcm(m, modulus)?;
+            // x + m * k is the answer, where m * k = difference (mod modulus)
+            let k = mulmod(difference / g, p, modulus / g);
+            let x = (x as i128 + m as i128 * k as i128).rem_euclid(combined as i128);
+            Some((x as i64, combined))
+        })
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_test() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        for (a, b) in [(240, 46), (-7, 3), (0, 5), (99, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn lcm_test() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        // a * b would overflow here, but the answer doesn't
+        let big = 1 << 40;
+        assert_eq!(lcm(big, big * 2), big * 2);
+        assert_eq!(checked_lcm(big, big * 2), Some(big * 2));
+        assert_eq!(checked_lcm(big + 1, big * 3), None);
+    }
+
+    #[test]
+    fn modular_test() {
+        let m = 119_315_717_514_047;
+        assert_eq!(mulmod(m - 1, m - 1, m), 1);
+        assert_eq!(mulmod(-3, 5, 7), 6);
+        assert_eq!(modpow(3, 4, 1000), 81);
+        assert_eq!(modpow(2, 0, 1), 0);
+        // Fermat's little theorem
+        assert_eq!(modpow(123_456_789, m as u64 - 1, m), 1);
+        assert_eq!(modinv(3, 7), Some(5));
+        assert_eq!(modinv(-3, 7), Some(2));
+        assert_eq!(modinv(4, 8), None);
+        let inverse = modinv(987_654_321, m).unwrap();
+        assert_eq!(mulmod(inverse, 987_654_321, m), 1);
+    }
+
+    #[test]
+    fn crt_test() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
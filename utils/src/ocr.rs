@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use crate::grid::Grid;
+use crate::render::{to_text, Palette};
+use crate::solution::SolutionError;
+
+// Reads the block capitals that some puzzles draw as their answer. There are two fonts: a small
+// one, 6 pixels high and usually 4 wide, and a large one, 10 pixels high and 6 wide. Letters are
+// separated by at least one blank column, so we split the picture up at those, then look up each
+// piece with its blank edges trimmed off.
+
+// fonts {{{1
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+// recognition {{{1
+// The letters drawn in `pixels`, where true is lit. None if the picture isn't one of the two
+// font heights, has something in it that isn't a letter, or has nothing drawn in it at all.
+pub fn recognise(pixels: &Grid<bool>) -> Option<String> {
+    let font = match pixels.height() {
+        6 => &SMALL[..],
+        10 => &LARGE[..],
+        _ => return None,
+    };
+    let glyphs: HashMap<String, char> = font
+        .iter()
+        .map(|&(letter, text)| {
+            let grid = Grid::parse(text, |c| c == '#');
+            (glyph_key(&grid, 0..grid.width()), letter)
+        })
+        .collect();
+    let blank: Vec<bool> = pixels.columns().map(|mut c| !c.any(|&p| p)).collect();
+    let mut result = String::new();
+    let mut x = 0;
+    while x < pixels.width() {
+        if blank[x] {
+            x += 1;
+            continue;
+        }
+        let end = (x..pixels.width())
+            .find(|&i| blank[i])
+            .unwrap_or(pixels.width());
+        result.push(*glyphs.get(&glyph_key(pixels, x..end))?);
+        x = end;
+    }
+    (!result.is_empty()).then_some(result)
+}
+
+// Like `recognise`, for a day's answer. If the letters can't be read, the error includes the
+// picture, so they can still be read by eye.
+pub fn read_answer(pixels: &Grid<bool>) -> Result<String, SolutionError> {
+    recognise(pixels).ok_or_else(|| {
+        let picture = to_text(pixels, &Palette::on_off());
+        SolutionError::no_answer(format!("couldn't read the letters in\n{}", picture))
+    })
+}
+
+// Like `recognise`, for text where `lit` marks the lit pixels
+pub fn recognise_text(text: &str, lit: char) -> Option<String> {
+    recognise(&Grid::parse(text.trim_end_matches('\n'), |c| c == lit))
+}
+
+// The given columns of the grid, as text
+fn glyph_key(pixels: &Grid<bool>, columns: std::ops::Range<usize>) -> String {
+    pixels
+        .rows()
+        .map(|row| {
+            row[columns.clone()]
+                .iter()
+                .map(|&p| if p { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lays glyphs out side by side with `gap` blank columns between them
+    fn draw(font: &[(char, &str)], word: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = word
+            .chars()
+            .map(|c| {
+                let (_, text) = font.iter().find(|&&(l, _)| l == c).unwrap();
+                text.lines().collect()
+            })
+            .collect();
+        (0..glyphs[0].len())
+            .map(|y| {
+                let row: Vec<_> = glyphs.iter().map(|g| g[y]).collect();
+                row.join(&".".repeat(gap)) + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn small_font() {
+        let every_letter: String = SMALL.iter().map(|&(c, _)| c).collect();
+        let text = draw(&SMALL, &every_letter, 1);
+        assert_eq!(recognise_text(&text, '#'), Some(every_letter));
+        // with some extra space round the outside
+        let padded: String = text.lines().map(|l| format!("..{}...\n", l)).collect();
+        assert_eq!(
+            recognise_text(&padded, '#').as_deref(),
+            Some("ABCEFGHIJKLOPRSUYZ")
+        );
+    }
+
+    #[test]
+    fn large_font() {
+        let every_letter: String = LARGE.iter().map(|&(c, _)| c).collect();
+        let text = draw(&LARGE, &every_letter, 2);
+        assert_eq!(recognise_text(&text, '#'), Some(every_letter));
+    }
+
+    #[test]
+    fn unrecognised() {
+        assert_eq!(recognise_text("#\n#\n#\n#\n#\n#\n", '#'), None);
+        assert_eq!(recognise_text("##\n##\n", '#'), None);
+        assert_eq!(recognise_text("......\n".repeat(6).as_str(), '#'), None);
+    }
+
+    #[test]
+    fn answers() {
+        let text = draw(&SMALL, "HI", 1);
+        let pixels = Grid::parse(text.trim_end(), |c| c == '#');
+        assert_eq!(read_answer(&pixels).as_deref(), Ok("HI"));
+        let error = read_answer(&Grid::from_vec(2, 2, vec![true, false, false, true]));
+        let expected = "couldn't read the letters in\n#.\n.#\n";
+        assert_eq!(error, Err(SolutionError::no_answer(expected)));
+        // a blank screen is an error too, not an empty answer
+        let blank = Grid::from_vec(4, 6, vec![false; 24]);
+        assert!(read_answer(&blank).is_err());
+    }
+}
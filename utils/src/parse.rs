@@ -0,0 +1,243 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+
+// Parsing puzzle input without panicking. Everything goes through an `Input`, which remembers
+// the whole text, so that when something goes wrong the error can say exactly where, given only
+// the piece of the text that was wrong.
+
+// error {{{1
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    // both counted from 1, like an editor would
+    pub line: usize,
+    pub column: usize,
+    // the whole line the problem was found on
+    pub excerpt: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "    {}", self.excerpt)?;
+        write!(f, "    {:>1$}", "^", self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+// input {{{1
+#[derive(Copy, Clone, Debug)]
+pub struct Input<'a> {
+    text: &'a str,
+}
+
+impl<'a> Input<'a> {
+    pub const fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    // An error pointing at the start of `at`, which should be a slice of the input text. If it
+    // isn't, the error points at the start of the input instead.
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .filter(|&offset| offset <= self.text.len())
+            .unwrap_or(0);
+        let line_start = self.text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.text[offset..]
+            .find('\n')
+            .map_or(self.text.len(), |i| offset + i);
+        ParseError {
+            line: self.text[..offset].matches('\n').count() + 1,
+            column: self.text[line_start..offset].chars().count() + 1,
+            excerpt: self.text[line_start..line_end].trim_end().to_string(),
+            message: message.into(),
+        }
+    }
+
+    // The non-blank lines, with surrounding whitespace removed
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+    }
+
+    pub fn number<T>(&self, s: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        s.trim()
+            .parse()
+            .map_err(|e| self.error(s, format!("expected a number, found `{}` ({})", s, e)))
+    }
+
+    pub fn split_once(
+        &self,
+        s: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delimiter)
+            .ok_or_else(|| self.error(s, format!("expected `{}`", delimiter)))
+    }
+
+    pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format!("expected `{}`", prefix)))
+    }
+
+    // The part of `s` inside a pair of brackets, like the `x=1` in `<x=1>`
+    pub fn bracketed(&self, s: &'a str, open: char, close: char) -> Result<&'a str, ParseError> {
+        let inner = s
+            .strip_prefix(open)
+            .ok_or_else(|| self.error(s, format!("expected `{}`", open)))?;
+        inner.strip_suffix(close).ok_or_else(|| {
+            let end = &s[s.len()..];
+            self.error(end, format!("expected `{}`", close))
+        })
+    }
+
+    // one per line {{{2
+    pub fn numbers_per_line<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.lines().map(|line| self.number(line)).collect()
+    }
+
+    // lists {{{2
+    // Items separated by `,` such as `1,2,3`, ignoring spaces around each one
+    pub fn comma_list<T>(&self, s: &'a str) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        s.split(',').map(|item| self.number(item)).collect()
+    }
+
+    // Pairs such as `x=1, y=-2`, in the order they appear
+    pub fn key_values<T>(&self, s: &'a str) -> Result<Vec<(&'a str, T)>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        s.split(',')
+            .map(|pair| {
+                let (key, value) = self.split_once(pair, "=")?;
+                Ok((key.trim(), self.number(value)?))
+            })
+            .collect()
+    }
+
+    // grids {{{2
+    // Every line is a row, and `cell` turns each character into a cell, or None if it isn't
+    // allowed. The rows all need to be the same length.
+    pub fn grid<T, CellFn>(&self, cell: CellFn) -> Result<Grid<T>, ParseError>
+    where
+        CellFn: Fn(char) -> Option<T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in self.text.lines().filter(|line| !line.trim().is_empty()) {
+            let line = line.trim_end();
+            let line_width = line.chars().count();
+            if *width.get_or_insert(line_width) != line_width {
+                let message = format!("expected {} cells, found {}", width.unwrap(), line_width);
+                return Err(self.error(line, message));
+            }
+            for (i, c) in line.char_indices() {
+                let value =
+                    cell(c).ok_or_else(|| self.error(&line[i..], format!("unexpected `{}`", c)))?;
+                cells.push(value);
+            }
+            height += 1;
+        }
+        Ok(Grid::from_vec(width.unwrap_or(0), height, cells))
+    }
+}
+
+// The most common shape of all: one number on each line
+pub fn numbers_per_line<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Input::new(input).numbers_per_line()
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::V2;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            numbers_per_line::<i64>("1\n-2\n\n 3 \n"),
+            Ok(vec![1, -2, 3])
+        );
+        let error = numbers_per_line::<i64>("1\n2\n3x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.excerpt, "3x");
+        let input = Input::new("a: 1,2, 3\nb: 4,five");
+        let lines: Vec<_> = input.lines().collect();
+        let (_, list) = input.split_once(lines[0], ": ").unwrap();
+        assert_eq!(input.comma_list::<u8>(list), Ok(vec![1, 2, 3]));
+        let (_, list) = input.split_once(lines[1], ": ").unwrap();
+        let error = input.comma_list::<u8>(list).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+    }
+
+    #[test]
+    fn structure() {
+        let input = Input::new("<x=1, y=-2>\n<x=3 y=4>\n(x=5)");
+        let lines: Vec<_> = input.lines().collect();
+        let inner = input.bracketed(lines[0], '<', '>').unwrap();
+        assert_eq!(input.key_values(inner), Ok(vec![("x", 1), ("y", -2)]));
+        let inner = input.bracketed(lines[1], '<', '>').unwrap();
+        let error = input.key_values::<i64>(inner).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        let error = input.bracketed(lines[2], '<', '>').unwrap_err();
+        assert_eq!(error.message, "expected `<`");
+        assert!(input.strip_prefix(lines[0], "<x").is_ok());
+        assert!(input.split_once(lines[2], "=").is_ok());
+    }
+
+    #[test]
+    fn grid() {
+        let wall = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let grid = Input::new("#.\n.#\n").grid(wall).unwrap();
+        assert!(grid[V2(1, 1)]);
+        let error = Input::new("#.\n.?\n").grid(wall).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.message, "unexpected `?`");
+        let error = Input::new("#.\n.#.\n").grid(wall).unwrap_err();
+        assert_eq!(error.message, "expected 2 cells, found 3");
+    }
+
+    #[test]
+    fn display() {
+        let error = numbers_per_line::<i64>("10\n 20 x\n").unwrap_err();
+        let expected =
+            "line 2, column 2: expected a number, found `20 x` (invalid digit found in string)
+     20 x
+     ^";
+        assert_eq!(error.to_string(), expected);
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::grid::Grid;
+use crate::v2::V2;
+
+// Turning grids into pictures, either as text for the terminal, or as PBM/PPM image files, which
+// almost any image viewer can open.
+
+// bounds {{{1
+// The smallest rectangle containing some points, with both corners included
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: V2,
+    pub max: V2,
+}
+
+impl Bounds {
+    pub fn of<'a>(points: impl IntoIterator<Item = &'a V2>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, &p| {
+            Some(match bounds {
+                None => Self { min: p, max: p },
+                Some(Self { min, max }) => Self {
+                    min: V2(min.0.min(p.0), min.1.min(p.1)),
+                    max: V2(max.0.max(p.0), max.1.max(p.1)),
+                },
+            })
+        })
+    }
+
+    pub const fn width(&self) -> usize {
+        (self.max.1 - self.min.1 + 1) as usize
+    }
+
+    pub const fn height(&self) -> usize {
+        (self.max.0 - self.min.0 + 1) as usize
+    }
+}
+
+// Which way up to draw the picture
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    // y increases down the screen, like the rest of the code base
+    Screen,
+    // y increases up the screen, like a graph
+    Cartesian,
+}
+
+// Fills in a dense grid just big enough for every point in `cells`, with `background` anywhere
+// that isn't mentioned
+pub fn densify<T: Clone>(
+    cells: &HashMap<V2, T>,
+    background: T,
+    orientation: Orientation,
+) -> Grid<T> {
+    let Some(bounds) = Bounds::of(cells.keys()) else {
+        return Grid::new(0, 0, background);
+    };
+    let mut grid = Grid::new(bounds.width(), bounds.height(), background);
+    for (&V2(y, x), value) in cells {
+        let row = match orientation {
+            Orientation::Screen => y - bounds.min.0,
+            Orientation::Cartesian => bounds.max.0 - y,
+        };
+        grid[V2(row, x - bounds.min.1)] = value.clone();
+    }
+    grid
+}
+
+// palette {{{1
+pub type Rgb = (u8, u8, u8);
+
+pub const BLACK: Rgb = (0, 0, 0);
+pub const WHITE: Rgb = (255, 255, 255);
+
+// How to draw each value, as a character in text and as a colour in images. Anything without
+// its own entry gets drawn with the fallback.
+#[derive(Clone, Debug)]
+pub struct Palette<T> {
+    entries: Vec<(T, char, Rgb)>,
+    fallback: (char, Rgb),
+}
+
+impl<T: PartialEq> Palette<T> {
+    pub const fn new(fallback: char, colour: Rgb) -> Self {
+        Self {
+            entries: Vec::new(),
+            fallback: (fallback, colour),
+        }
+    }
+
+    pub fn with(mut self, value: T, c: char, colour: Rgb) -> Self {
+        self.entries.push((value, c, colour));
+        self
+    }
+
+    fn lookup(&self, value: &T) -> (char, Rgb) {
+        self.entries
+            .iter()
+            .find(|(v, _, _)| v == value)
+            .map_or(self.fallback, |&(_, c, colour)| (c, colour))
+    }
+
+    pub fn char_for(&self, value: &T) -> char {
+        self.lookup(value).0
+    }
+
+    pub fn colour_for(&self, value: &T) -> Rgb {
+        self.lookup(value).1
+    }
+}
+
+impl Palette<bool> {
+    // `#` and white for true, `.` and black for false
+    pub fn on_off() -> Self {
+        Self::new('.', BLACK).with(true, '#', WHITE)
+    }
+}
+
+// output {{{1
+// One line per row, each ending in a newline
+pub fn to_text<T: PartialEq>(grid: &Grid<T>, palette: &Palette<T>) -> String {
+    grid.rows()
+        .flat_map(|row| {
+            row.iter()
+                .map(|value| palette.char_for(value))
+                .chain(['\n'])
+        })
+        .collect()
+}
+
+// A black and white image, in the plain text version of the format. Pixels where `on` is true
+// are black, as PBM expects.
+pub fn write_pbm<T, W, OnFn>(mut out: W, grid: &Grid<T>, on: OnFn) -> io::Result<()>
+where
+    W: Write,
+    OnFn: Fn(&T) -> bool,
+{
+    writeln!(out, "P1\n{} {}", grid.width(), grid.height())?;
+    for row in grid.rows() {
+        let pixels: Vec<_> = row.iter().map(|v| if on(v) { "1" } else { "0" }).collect();
+        writeln!(out, "{}", pixels.join(" "))?;
+    }
+    Ok(())
+}
+
+// A colour image, in the plain text version of the format, using the palette's colours
+pub fn write_ppm<T, W>(mut out: W, grid: &Grid<T>, palette: &Palette<T>) -> io::Result<()>
+where
+    T: PartialEq,
+    W: Write,
+{
+    writeln!(out, "P3\n{} {}\n255", grid.width(), grid.height())?;
+    for row in grid.rows() {
+        let pixels: Vec<_> = row
+            .iter()
+            .map(|v| {
+                let (r, g, b) = palette.colour_for(v);
+                format!("{} {} {}", r, g, b)
+            })
+            .collect();
+        writeln!(out, "{}", pixels.join("  "))?;
+    }
+    Ok(())
+}
+
+pub fn save_ppm<T: PartialEq>(
+    path: impl AsRef<Path>,
+    grid: &Grid<T>,
+    palette: &Palette<T>,
+) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    write_ppm(&mut out, grid, palette)?;
+    out.flush()
+}
+
+// For the grids of lit pixels that some puzzles draw their answers with
+pub fn save_pbm(path: impl AsRef<Path>, grid: &Grid<bool>) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    write_pbm(&mut out, grid, |&on| on)?;
+    out.flush()
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells() -> HashMap<V2, u8> {
+        HashMap::from([(V2(-1, 2), 1), (V2(0, 3), 2), (V2(1, 2), 1)])
+    }
+
+    fn palette() -> Palette<u8> {
+        Palette::new('?', BLACK)
+            .with(0, ' ', BLACK)
+            .with(1, '#', WHITE)
+    }
+
+    #[test]
+    fn bounds() {
+        let bounds = Bounds::of(cells().keys()).unwrap();
+        assert_eq!(bounds.min, V2(-1, 2));
+        assert_eq!(bounds.max, V2(1, 3));
+        assert_eq!((bounds.width(), bounds.height()), (2, 3));
+        assert_eq!(Bounds::of(&[]), None);
+    }
+
+    #[test]
+    fn text() {
+        let screen = densify(&cells(), 0, Orientation::Screen);
+        assert_eq!(to_text(&screen, &palette()), "# \n ?\n# \n");
+        let mut cells = cells();
+        cells.insert(V2(1, 3), 1);
+        let graph = densify(&cells, 0, Orientation::Cartesian);
+        assert_eq!(to_text(&graph, &palette()), "##\n ?\n# \n");
+        let empty = densify(&HashMap::new(), 0, Orientation::Screen);
+        assert_eq!(to_text(&empty, &palette()), "");
+        let bools = Grid::from_vec(2, 1, vec![true, false]);
+        assert_eq!(to_text(&bools, &Palette::on_off()), "#.\n");
+    }
+
+    #[test]
+    fn images() {
+        let grid = densify(&cells(), 0, Orientation::Screen);
+        let mut pbm = Vec::new();
+        write_pbm(&mut pbm, &grid, |&v| v != 0).unwrap();
+        assert_eq!(String::from_utf8(pbm).unwrap(), "P1\n2 3\n1 0\n0 1\n1 0\n");
+        let mut ppm = Vec::new();
+        write_ppm(&mut ppm, &Grid::from_vec(2, 1, vec![1, 0]), &palette()).unwrap();
+        let expected = "P3\n2 1\n255\n255 255 255  0 0 0\n";
+        assert_eq!(String::from_utf8(ppm).unwrap(), expected);
+    }
+
+    #[test]
+    fn save() {
+        let path = std::env::temp_dir().join(format!("render-test-{}.pbm", std::process::id()));
+        let grid = Grid::from_vec(3, 2, vec![true, false, true, false, true, false]);
+        save_pbm(&path, &grid).unwrap();
+        let saved = std::fs::read_to_string(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(saved.unwrap(), "P1\n3 2\n1 0 1\n0 1 0\n");
+    }
+}
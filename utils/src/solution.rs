@@ -0,0 +1,245 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use crate::parse::ParseError;
+
+// The shape every day has in common, so that tools can run any of them without knowing what
+// their answers look like. `parse` turns away input a day can't use, and keeps whatever the parts
+// need. For most days that's just the text, which each part then reads for itself.
+
+// error {{{1
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolutionError {
+    Parse(ParseError),
+    // the input made sense, but there's no answer in it
+    NoAnswer(String),
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "couldn't parse the input: {}", e),
+            Self::NoAnswer(message) => write!(f, "no answer: {}", message),
+        }
+    }
+}
+
+impl Error for SolutionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parse(e) => Some(e),
+            Self::NoAnswer(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for SolutionError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl SolutionError {
+    pub fn no_answer(message: impl Into<String>) -> Self {
+        Self::NoAnswer(message.into())
+    }
+}
+
+// trait {{{1
+pub trait Solution: Sized {
+    const DAY: u8;
+    // The parts that have answers. That's both of them, apart from the very last day, which is
+    // played by hand.
+    const PARTS: &'static [Part] = &[Part::A, Part::B];
+
+    fn parse(input: &str) -> Result<Self, SolutionError>;
+    fn part_a(&self) -> Result<impl Display, SolutionError>;
+    fn part_b(&self) -> Result<impl Display, SolutionError>;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Self::A => "a",
+            Self::B => "b",
+        })
+    }
+}
+
+// entries {{{1
+// A `Solution` with its types erased, so different days can sit in the same list
+#[derive(Copy, Clone)]
+pub struct Entry {
+    pub day: u8,
+    pub parts: &'static [Part],
+    part_a: fn(&str) -> Result<String, SolutionError>,
+    part_b: fn(&str) -> Result<String, SolutionError>,
+}
+
+impl Entry {
+    // Every run parses the input afresh, so each part's time includes the parsing
+    pub fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            parts: S::PARTS,
+            part_a: |input| Ok(S::parse(input)?.part_a()?.to_string()),
+            part_b: |input| Ok(S::parse(input)?.part_b()?.to_string()),
+        }
+    }
+
+    pub fn run(&self, part: Part, input: &str) -> Result<String, SolutionError> {
+        match part {
+            Part::A => (self.part_a)(input),
+            Part::B => (self.part_b)(input),
+        }
+    }
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Entry").field("day", &self.day).finish()
+    }
+}
+
+// macro {{{1
+// Declares a day whose parts work straight from the input text, which is most of them. Each part
+// is an expression giving its `Result`, with the input bound to the name given. With no parts
+// given, they're `part_a` and `part_b` from the same module, which can't fail. `check` runs when
+// the input is parsed, so that input the parts would choke on is turned away with an error. What
+// it returns is thrown away; only the text is kept, and each part parses that again.
+//
+//     utils::solution!(Day05 = 5, check IntcodeComputer::try_parse_program);
+//     utils::solution! {
+//         Day06 = 6;
+//         a: |input| Ok(part_a(input)),
+//         b: |input| part_b(input).ok_or_else(|| SolutionError::no_answer("...")),
+//     }
+#[macro_export]
+macro_rules! solution {
+    ($name:ident = $day:literal $(, check $check:path)?) => {
+        $crate::solution! {
+            $name = $day $(, check $check)?;
+            a: |input| Ok(part_a(input)),
+            b: |input| Ok(part_b(input)),
+        }
+    };
+    (
+        $name:ident = $day:literal $(, check $check:path)?;
+        a: |$a_input:ident| $a:expr,
+        b: |$b_input:ident| $b:expr $(,)?
+    ) => {
+        pub struct $name {
+            input: String,
+        }
+
+        impl $crate::solution::Solution for $name {
+            const DAY: u8 = $day;
+
+            fn parse(input: &str) -> Result<Self, $crate::solution::SolutionError> {
+                $($check(input)?;)?
+                Ok(Self {
+                    input: input.to_string(),
+                })
+            }
+
+            fn part_a(
+                &self,
+            ) -> Result<impl ::std::fmt::Display, $crate::solution::SolutionError> {
+                let $a_input: &str = &self.input;
+                $a
+            }
+
+            fn part_b(
+                &self,
+            ) -> Result<impl ::std::fmt::Display, $crate::solution::SolutionError> {
+                let $b_input: &str = &self.input;
+                $b
+            }
+        }
+    };
+}
+
+// tests {{{1
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::numbers_per_line;
+
+    struct Sum(Vec<i64>);
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+
+        fn parse(input: &str) -> Result<Self, SolutionError> {
+            Ok(Self(numbers_per_line(input)?))
+        }
+
+        fn part_a(&self) -> Result<impl Display, SolutionError> {
+            Ok(self.0.iter().sum::<i64>())
+        }
+
+        fn part_b(&self) -> Result<impl Display, SolutionError> {
+            self.0
+                .iter()
+                .max()
+                .map(|n| format!("max {}", n))
+                .ok_or_else(|| SolutionError::no_answer("nothing to compare"))
+        }
+    }
+
+    // a day declared with the macro, as they all are
+    mod day {
+        use crate::parse::{numbers_per_line, ParseError};
+
+        pub fn part_a(input: &str) -> usize {
+            input.lines().count()
+        }
+
+        pub fn part_b(input: &str) -> usize {
+            input.len()
+        }
+
+        fn check(input: &str) -> Result<Vec<i64>, ParseError> {
+            numbers_per_line(input)
+        }
+
+        crate::solution!(Day = 1, check check);
+    }
+
+    #[test]
+    fn entry() {
+        let entry = Entry::of::<Sum>();
+        assert_eq!(entry.day, 0);
+        assert_eq!(entry.parts, [Part::A, Part::B]);
+        assert_eq!(entry.run(Part::A, "1\n2\n3\n"), Ok("6".to_string()));
+        assert_eq!(entry.run(Part::B, "1\n2\n3\n"), Ok("max 3".to_string()));
+        assert_eq!(
+            entry.run(Part::B, "").unwrap_err().to_string(),
+            "no answer: nothing to compare"
+        );
+        let error = entry.run(Part::A, "1\nx\n").unwrap_err();
+        assert!(matches!(
+            error,
+            SolutionError::Parse(ParseError { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn declared_with_the_macro() {
+        let entry = Entry::of::<day::Day>();
+        assert_eq!((entry.day, entry.parts), (1, &[Part::A, Part::B][..]));
+        assert_eq!(entry.run(Part::A, "1\n2\n"), Ok("2".to_string()));
+        assert_eq!(entry.run(Part::B, "1\n2\n"), Ok("4".to_string()));
+        let error = entry.run(Part::B, "1\nx\n").unwrap_err();
+        assert!(matches!(
+            error,
+            SolutionError::Parse(ParseError { line: 2, .. })
+        ));
+    }
+}
@@ -8,12 +8,12 @@ pub struct V2(pub i64, pub i64);
 
 impl V2 {
     pub const fn taxicab_neighbours(self) -> [Self; 4] {
-        let Self(x, y) = self;
+        let Self(y, x) = self;
         [
-            Self(x + 1, y),
-            Self(x - 1, y),
-            Self(x, y - 1),
-            Self(x, y + 1),
+            Self(y + 1, x),
+            Self(y - 1, x),
+            Self(y, x - 1),
+            Self(y, x + 1),
         ]
     }
 
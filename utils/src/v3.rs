@@ -1,12 +1,121 @@
-use std::ops::{AddAssign, Sub};
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Default)]
 pub struct V3(pub i64, pub i64, pub i64);
 
 impl V3 {
     pub const fn signum(self) -> Self {
         Self(self.0.signum(), self.1.signum(), self.2.signum())
     }
+
+    // The sum of the absolute values of each component, i.e. the distance from the origin
+    pub const fn manhattan(self) -> i64 {
+        self.0.abs() + self.1.abs() + self.2.abs()
+    }
+
+    pub const fn manhattan_distance(self, other: Self) -> i64 {
+        Self(other.0 - self.0, other.1 - self.1, other.2 - self.2).manhattan()
+    }
+
+    pub const fn dot(self, other: Self) -> i64 {
+        self.0 * other.0 + self.1 * other.1 + self.2 * other.2
+    }
+
+    pub const fn cross(self, other: Self) -> Self {
+        Self(
+            self.1 * other.2 - self.2 * other.1,
+            self.2 * other.0 - self.0 * other.2,
+            self.0 * other.1 - self.1 * other.0,
+        )
+    }
+
+    // Component-wise minimum. Not `min`, which `Ord` already has, comparing whole vectors.
+    pub fn component_min(self, other: Self) -> Self {
+        Self(
+            self.0.min(other.0),
+            self.1.min(other.1),
+            self.2.min(other.2),
+        )
+    }
+
+    // Component-wise maximum
+    pub fn component_max(self, other: Self) -> Self {
+        Self(
+            self.0.max(other.0),
+            self.1.max(other.1),
+            self.2.max(other.2),
+        )
+    }
+
+    // The points one step away along a single axis
+    pub const fn neighbours6(self) -> [Self; 6] {
+        let Self(x, y, z) = self;
+        [
+            Self(x + 1, y, z),
+            Self(x - 1, y, z),
+            Self(x, y + 1, z),
+            Self(x, y - 1, z),
+            Self(x, y, z + 1),
+            Self(x, y, z - 1),
+        ]
+    }
+
+    // Every point in the surrounding 3x3x3 cube, apart from this one
+    pub fn neighbours26(self) -> impl Iterator<Item = Self> {
+        (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| Self(dx, dy, dz))))
+            .filter(|&offset| offset != Self(0, 0, 0))
+            .map(move |offset| self + offset)
+    }
+}
+
+impl fmt::Debug for V3 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("V3")
+            .field(&self.0)
+            .field(&self.1)
+            .field(&self.2)
+            .finish()
+    }
+}
+
+impl fmt::Display for V3 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "({}, {}, {})", self.0, self.1, self.2)
+    }
+}
+
+impl Index<usize> for V3 {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        match axis {
+            0 => &self.0,
+            1 => &self.1,
+            2 => &self.2,
+            _ => panic!("V3 has no axis {}", axis),
+        }
+    }
+}
+
+impl IndexMut<usize> for V3 {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        match axis {
+            0 => &mut self.0,
+            1 => &mut self.1,
+            2 => &mut self.2,
+            _ => panic!("V3 has no axis {}", axis),
+        }
+    }
+}
+
+impl Add for V3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
 }
 
 impl AddAssign for V3 {
@@ -17,6 +126,36 @@ impl AddAssign for V3 {
     }
 }
 
+impl Mul<i64> for V3 {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs, self.2 * rhs)
+    }
+}
+
+impl Mul<V3> for i64 {
+    type Output = V3;
+
+    fn mul(self, rhs: V3) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl MulAssign<i64> for V3 {
+    fn mul_assign(&mut self, rhs: i64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Neg for V3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1, -self.2)
+    }
+}
+
 impl Sub for V3 {
     type Output = Self;
 
@@ -24,3 +163,66 @@ impl Sub for V3 {
         Self(self.0 - other.0, self.1 - other.1, self.2 - other.2)
     }
 }
+
+impl SubAssign for V3 {
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0;
+        self.1 -= other.1;
+        self.2 -= other.2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = V3(1, -2, 3);
+        let b = V3(4, 5, -6);
+        assert_eq!(a + b, V3(5, 3, -3));
+        assert_eq!(a - b, V3(-3, -7, 9));
+        assert_eq!(a * 2, V3(2, -4, 6));
+        assert_eq!(2 * a, a * 2);
+        assert_eq!(-a, V3(-1, 2, -3));
+        let mut c = a;
+        c += b;
+        c -= b;
+        c *= 3;
+        assert_eq!(c, V3(3, -6, 9));
+    }
+
+    #[test]
+    fn geometry() {
+        let a = V3(1, -2, 3);
+        let b = V3(4, 5, -6);
+        assert_eq!(a.manhattan(), 6);
+        assert_eq!(a.manhattan_distance(b), 3 + 7 + 9);
+        assert_eq!(a.dot(b), 4 - 10 - 18);
+        assert_eq!(V3(1, 0, 0).cross(V3(0, 1, 0)), V3(0, 0, 1));
+        assert_eq!(a.cross(b).dot(a), 0);
+        assert_eq!(a.component_min(b), V3(1, -2, -6));
+        assert_eq!(a.component_max(b), V3(4, 5, 3));
+        // while `min` is still the one from `Ord`
+        assert_eq!(a.min(b), a);
+    }
+
+    #[test]
+    fn axes() {
+        let mut a = V3(1, 2, 3);
+        assert_eq!((a[0], a[1], a[2]), (1, 2, 3));
+        a[1] = 7;
+        assert_eq!(a, V3(1, 7, 3));
+        assert_eq!(a.to_string(), "(1, 7, 3)");
+    }
+
+    #[test]
+    fn neighbours() {
+        let origin = V3(0, 0, 0);
+        assert!(origin.neighbours6().iter().all(|n| n.manhattan() == 1));
+        let all: Vec<_> = V3(5, 5, 5).neighbours26().collect();
+        assert_eq!(all.len(), 26);
+        assert!(!all.contains(&V3(5, 5, 5)));
+        assert!(all.contains(&V3(4, 6, 5)));
+    }
+}
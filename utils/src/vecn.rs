@@ -0,0 +1,237 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::v2::V2;
+use crate::v3::V3;
+
+// An integer vector with any number of dimensions. Conversions to and from V2 and V3 keep the
+// components in the same order, so a VecN<2> made from a V2 is still (y, x).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct VecN<const N: usize>(pub [i64; N]);
+
+impl<const N: usize> VecN<N> {
+    pub const fn zero() -> Self {
+        Self([0; N])
+    }
+
+    // The vector with a 1 along `axis` and 0 everywhere else
+    pub const fn unit(axis: usize) -> Self {
+        let mut components = [0; N];
+        components[axis] = 1;
+        Self(components)
+    }
+
+    pub fn manhattan(self) -> i64 {
+        self.0.iter().map(|c| c.abs()).sum()
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> i64 {
+        (other - self).manhattan()
+    }
+
+    pub fn signum(self) -> Self {
+        Self(self.0.map(i64::signum))
+    }
+
+    // The 2N points one step away along a single axis
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |axis| [self + Self::unit(axis), self - Self::unit(axis)])
+    }
+
+    // All 3^N - 1 points that differ by at most one in every component
+    pub fn neighbours_with_diagonals(self) -> impl Iterator<Item = Self> {
+        (0..3usize.pow(N as u32))
+            .map(|mut i| {
+                let mut offset = Self::zero();
+                for axis in 0..N {
+                    offset[axis] = (i % 3) as i64 - 1;
+                    i /= 3;
+                }
+                offset
+            })
+            .filter(|&offset| offset != Self::zero())
+            .map(move |offset| self + offset)
+    }
+}
+
+impl<const N: usize> Default for VecN<N> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<const N: usize> fmt::Debug for VecN<N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut tuple = fmt.debug_tuple("VecN");
+        for c in &self.0 {
+            tuple.field(c);
+        }
+        tuple.finish()
+    }
+}
+
+impl<const N: usize> fmt::Display for VecN<N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(fmt, ", ")?;
+            }
+            write!(fmt, "{}", c)?;
+        }
+        write!(fmt, ")")
+    }
+}
+
+impl<const N: usize> Index<usize> for VecN<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for VecN<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Add for VecN<N> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<const N: usize> AddAssign for VecN<N> {
+    fn add_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<const N: usize> Sub for VecN<N> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<const N: usize> SubAssign for VecN<N> {
+    fn sub_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<const N: usize> Mul<i64> for VecN<N> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: i64) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<const N: usize> Mul<VecN<N>> for i64 {
+    type Output = VecN<N>;
+
+    fn mul(self, rhs: VecN<N>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<const N: usize> MulAssign<i64> for VecN<N> {
+    fn mul_assign(&mut self, rhs: i64) {
+        for a in &mut self.0 {
+            *a *= rhs;
+        }
+    }
+}
+
+impl<const N: usize> Neg for VecN<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for VecN<N> {
+    fn from(components: [i64; N]) -> Self {
+        Self(components)
+    }
+}
+
+impl From<V2> for VecN<2> {
+    fn from(V2(a, b): V2) -> Self {
+        Self([a, b])
+    }
+}
+
+impl From<VecN<2>> for V2 {
+    fn from(VecN([a, b]): VecN<2>) -> Self {
+        Self(a, b)
+    }
+}
+
+impl From<V3> for VecN<3> {
+    fn from(V3(a, b, c): V3) -> Self {
+        Self([a, b, c])
+    }
+}
+
+impl From<VecN<3>> for V3 {
+    fn from(VecN([a, b, c]): VecN<3>) -> Self {
+        Self(a, b, c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = VecN([1, -2, 3, 4]);
+        let b = VecN([0, 5, -6, 1]);
+        assert_eq!(a + b, VecN([1, 3, -3, 5]));
+        assert_eq!(a - b, VecN([1, -7, 9, 3]));
+        assert_eq!(a * 2, 2 * a);
+        assert_eq!(-a, VecN([-1, 2, -3, -4]));
+        assert_eq!(a.manhattan(), 10);
+        assert_eq!(a.manhattan_distance(b), 1 + 7 + 9 + 3);
+        assert_eq!(a.to_string(), "(1, -2, 3, 4)");
+    }
+
+    #[test]
+    fn neighbours() {
+        let origin = VecN::<4>::zero();
+        assert_eq!(origin.neighbours().count(), 8);
+        assert!(origin.neighbours().all(|n| n.manhattan() == 1));
+        let all: Vec<_> = origin.neighbours_with_diagonals().collect();
+        assert_eq!(all.len(), 80);
+        assert!(all.contains(&VecN([-1, 1, 0, 1])));
+        assert!(!all.contains(&origin));
+    }
+
+    #[test]
+    fn conversions() {
+        let v = VecN::from(V2(3, 4));
+        assert_eq!(v, VecN([3, 4]));
+        assert_eq!(V2::from(v), V2(3, 4));
+        assert_eq!(V3::from(VecN::from(V3(1, 2, 3))), V3(1, 2, 3));
+        let mut neighbours: Vec<_> = v.neighbours().map(V2::from).collect();
+        let mut expected = V2(3, 4).taxicab_neighbours().to_vec();
+        neighbours.sort();
+        expected.sort();
+        assert_eq!(neighbours, expected);
+    }
+}